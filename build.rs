@@ -0,0 +1,37 @@
+//! Generates the table of inputs embedded with the `embed-inputs` feature,
+//! listing every `input/dayNN.txt` that exists at build time
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let embed = env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some();
+
+    let mut entries = String::new();
+    if embed {
+        // Cargo considers a missing path changed, so without the directory the crate is
+        // rebuilt every time, picking the inputs up as soon as they're added
+        println!("cargo::rerun-if-changed=input");
+        if !Path::new(&manifest_dir).join("input").is_dir() {
+            println!("cargo::warning=input/ doesn't exist, no inputs are embedded");
+        }
+        for day in 1..=25 {
+            let path = Path::new(&manifest_dir).join(format!("input/day{:0>2}.txt", day));
+            if path.is_file() {
+                entries.push_str(&format!("    ({}, include_str!({:?})),\n", day, path));
+            }
+        }
+    }
+
+    // Rewriting an unchanged file would rebuild the crate every time the script runs
+    let path = Path::new(&out_dir).join("embedded_inputs.rs");
+    let content = format!("pub const EMBEDDED: &[(u8, &str)] = &[\n{}];\n", entries);
+    if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+        fs::write(&path, content).unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ * C interface of the Advent of Code 2025 solvers, implemented by
+ * libadvent_of_code_2025 (built with `cargo build --release --lib`).
+ *
+ * Every function takes the puzzle input as `len` bytes of UTF-8 at `input`
+ * (which may be NULL when `len` is 0) and returns one of the AOC_* status codes.
+ * Unless the status is AOC_ERR_NULL, a NUL-terminated string is written to `*out`:
+ * the answer on AOC_OK, otherwise an error message. The caller owns that string
+ * and must release it with aoc_free_string. The functions never unwind into the
+ * caller and can be called from several threads at once.
+ */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The answer was written to *out */
+#define AOC_OK 0
+/* input or out was NULL, nothing was written to *out */
+#define AOC_ERR_NULL 1
+/* The input isn't valid UTF-8 */
+#define AOC_ERR_UTF8 2
+/* Parsing the input or solving the part failed */
+#define AOC_ERR_SOLVE 3
+/* The solver panicked */
+#define AOC_ERR_PANIC 4
+
+/* Release a string written to *out by one of the solvers. NULL is ignored. */
+void aoc_free_string(char *s);
+
+int32_t aoc_day01_part1(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day01_part2(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day02_part1(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day02_part2(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day03_part1(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day03_part2(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day04_part1(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day04_part2(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day05_part1(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day05_part2(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day06_part1(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day06_part2(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day07_part1(const uint8_t *input, size_t len, char **out);
+int32_t aoc_day07_part2(const uint8_t *input, size_t len, char **out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
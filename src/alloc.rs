@@ -0,0 +1,177 @@
+//! Allocation counting, enabled with the `alloc-count` feature which installs
+//! a global allocator that keeps per-thread statistics on top of the system allocator.
+
+/// Heap usage of a measured piece of code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included
+    pub allocations: usize,
+    /// Total number of bytes requested
+    pub bytes: usize,
+    /// Highest number of bytes allocated at the same time, on top of what was already live
+    pub peak: usize,
+}
+
+/// Run `f` and report the allocations it made on the current thread,
+/// `None` if the `alloc-count` feature is disabled
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    #[cfg(feature = "alloc-count")]
+    {
+        let (result, stats) = counting::measure(f);
+        (result, Some(stats))
+    }
+    #[cfg(not(feature = "alloc-count"))]
+    {
+        (f(), None)
+    }
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(feature = "alloc-count")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use super::AllocStats;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    struct CountingAllocator;
+
+    // Const initialised cells without destructors, so that accessing them never allocates
+    // and they stay usable for the whole lifetime of the thread.
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+        static BYTES: Cell<usize> = const { Cell::new(0) };
+        static CURRENT: Cell<isize> = const { Cell::new(0) };
+        static PEAK: Cell<isize> = const { Cell::new(0) };
+    }
+
+    fn record_alloc(size: usize) {
+        let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+        let _ = BYTES.try_with(|b| b.set(b.get().wrapping_add(size)));
+        resize(size as isize);
+    }
+
+    fn resize(delta: isize) {
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get().wrapping_add(delta));
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            resize(-(layout.size() as isize));
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+                let _ = BYTES.try_with(|b| b.set(b.get().wrapping_add(new_size)));
+                resize(new_size as isize - layout.size() as isize);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+        let allocations = ALLOCATIONS.with(Cell::get);
+        let bytes = BYTES.with(Cell::get);
+        let current = CURRENT.with(Cell::get);
+        PEAK.with(|peak| peak.set(current));
+
+        let result = f();
+
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.with(Cell::get) - allocations,
+            bytes: BYTES.with(Cell::get).wrapping_sub(bytes),
+            peak: (PEAK.with(Cell::get) - current).max(0) as usize,
+        };
+
+        (result, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[cfg(not(feature = "alloc-count"))]
+    #[test]
+    fn test_measure_without_feature_returns_none() {
+        assert_eq!(measure(|| 42), (42, None));
+    }
+
+    #[cfg(feature = "alloc-count")]
+    #[test]
+    fn test_measure_counts_allocations_and_peak() {
+        let (len, stats) = measure(|| {
+            let first = vec![0u8; 1000];
+            drop(first);
+            let second = vec![0u8; 500];
+            second.len()
+        });
+        let stats = stats.unwrap();
+
+        assert_eq!(len, 500);
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 1500);
+        assert_eq!(stats.peak, 1000);
+    }
+
+    #[cfg(feature = "alloc-count")]
+    #[test]
+    fn test_measure_counts_reallocations() {
+        let (_, stats) = measure(|| {
+            let mut v: Vec<u8> = Vec::with_capacity(10);
+            v.reserve_exact(100);
+            v
+        });
+
+        assert_eq!(stats.unwrap().allocations, 2);
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+
+use crate::days::Day;
+use crate::solution::Params;
+
+/// Answer and timing samples of a single part solved repeatedly
+#[derive(Debug)]
+pub struct PartBench {
+    pub answer: String,
+    pub samples: Vec<Duration>,
+}
+
+impl PartBench {
+    pub fn median(&self) -> Duration {
+        median(&self.samples)
+    }
+}
+
+/// Solve `part` of `day` `samples` times, failing on the first error
+pub fn bench_part(day: &Day, input: &str, part: u8, samples: usize) -> Result<PartBench> {
+    let mut answer = String::new();
+    let mut durations = Vec::with_capacity(samples);
+
+    for _ in 0..samples.max(1) {
+        let mut report = (day.solve)(input, &[part], false, &Params::default())?;
+        let part_report = report
+            .parts
+            .pop()
+            .ok_or_else(|| anyhow!("Part {} was not solved", part))?;
+        answer = part_report.answer?;
+        durations.push(part_report.elapsed);
+    }
+
+    Ok(PartBench {
+        answer,
+        samples: durations,
+    })
+}
+
+/// Median of the durations, the mean of the middle two for an even count
+pub fn median(durations: &[Duration]) -> Duration {
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+
+    match sorted.len() {
+        0 => Duration::ZERO,
+        len if len % 2 == 1 => sorted[len / 2],
+        len => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::find_day;
+
+    #[test]
+    fn test_median_odd_and_even() {
+        let ms = Duration::from_millis;
+
+        assert_eq!(median(&[ms(5), ms(1), ms(3)]), ms(3));
+        assert_eq!(median(&[ms(4), ms(1), ms(2), ms(8)]), ms(3));
+        assert_eq!(median(&[]), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bench_part_collects_samples() {
+        let day = find_day(1).unwrap();
+        let bench = bench_part(
+            day,
+            "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n",
+            2,
+            5,
+        )
+        .unwrap();
+
+        assert_eq!(bench.answer, "6");
+        assert_eq!(bench.samples.len(), 5);
+    }
+
+    #[test]
+    fn test_bench_part_returns_error() {
+        let day = find_day(1).unwrap();
+
+        assert!(bench_part(day, "X1\n", 1, 5).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2025::days::{DAYS, find_day};
+use advent_of_code_2025::fetch::{DEFAULT_BASE_URL, FetchConfig, fetch};
+use advent_of_code_2025::history::{print_comparison, record_bench};
+use advent_of_code_2025::readme::update_readme;
+use advent_of_code_2025::repl::repl;
+use advent_of_code_2025::runner::{Command, list_embedded, parse_args, print_export, run};
+use advent_of_code_2025::serve::serve;
+use advent_of_code_2025::watch::watch;
+use anyhow::{Context, Result};
+
+fn main() -> Result<()> {
+    match parse_args(std::env::args().skip(1))? {
+        Command::Run(options) => run(&options),
+        Command::Watch(day) => watch(day),
+        Command::Repl(day) => repl(day),
+        Command::Embedded => {
+            list_embedded();
+            Ok(())
+        }
+        Command::Export {
+            day,
+            name,
+            input,
+            params,
+        } => print_export(day, name.as_deref(), input.as_deref(), &params),
+        Command::Serve { addr, limits } => serve(&addr, limits),
+        Command::Fetch {
+            days,
+            base_url,
+            session_file,
+            interval,
+            force,
+        } => {
+            let days = if days.is_empty() {
+                DAYS.iter().map(|day| day.day).collect()
+            } else {
+                days
+            };
+            let config = FetchConfig {
+                base_url: base_url
+                    .or_else(|| std::env::var("AOC_BASE_URL").ok())
+                    .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+                session: std::env::var("AOC_SESSION").ok(),
+                session_file: PathBuf::from(session_file),
+                dir: PathBuf::from("input"),
+                interval,
+                force,
+            };
+            fetch(&days, config)
+        }
+        Command::Readme { path, samples } => update_readme(&path, samples),
+        Command::Bench {
+            days,
+            samples,
+            history,
+        } => {
+            let days = if days.is_empty() {
+                DAYS.iter().collect()
+            } else {
+                days.into_iter()
+                    .map(|day| {
+                        find_day(day).with_context(|| format!("Day {} is not solved yet", day))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            };
+            record_bench(&days, samples, Path::new(&history))
+        }
+        Command::Compare {
+            history,
+            baseline,
+            threshold,
+        } => print_comparison(Path::new(&history), baseline.as_deref(), threshold),
+    }
+}
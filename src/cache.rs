@@ -0,0 +1,131 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+/// Default directory of the answer cache, relative to the working directory
+pub const DEFAULT_CACHE_DIR: &str = ".aoc-cache";
+
+/// Identifies a cached answer: the day and part, the solver version and the input contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheKey {
+    pub day: u8,
+    pub part: u8,
+    pub version: u32,
+    pub input_hash: u64,
+}
+
+impl CacheKey {
+    fn filename(&self) -> String {
+        format!(
+            "day{:0>2}-part{}-v{}-{:016x}.txt",
+            self.day, self.part, self.version, self.input_hash
+        )
+    }
+}
+
+/// On-disk cache of answers, one file per `CacheKey`
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<String> {
+        fs::read_to_string(self.dir.join(key.filename())).ok()
+    }
+
+    pub fn put(&self, key: &CacheKey, answer: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Could not create cache directory: {}", self.dir.display()))?;
+        let path = self.dir.join(key.filename());
+        fs::write(&path, answer)
+            .with_context(|| format!("Could not write cache file: {}", path.display()))
+    }
+
+    /// Remove every cached answer
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err).with_context(|| {
+                format!("Could not remove cache directory: {}", self.dir.display())
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Hash the input contents with 64-bit FNV-1a, which unlike `DefaultHasher`
+/// is guaranteed to stay the same between Rust versions
+pub fn hash_input(input: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    input.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str) -> Cache {
+        let dir =
+            std::env::temp_dir().join(format!("aoc-cache-test-{}-{}", name, std::process::id()));
+        let cache = Cache::new(dir);
+        cache.clear().unwrap();
+        cache
+    }
+
+    fn key(part: u8, version: u32, input: &str) -> CacheKey {
+        CacheKey {
+            day: 1,
+            part,
+            version,
+            input_hash: hash_input(input),
+        }
+    }
+
+    #[test]
+    fn test_hash_input_matches_known_fnv1a_values() {
+        assert_eq!(hash_input(""), 0xcbf29ce484222325);
+        assert_eq!(hash_input("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_cache_put_and_get() {
+        let cache = temp_cache("put-get");
+
+        assert_eq!(cache.get(&key(1, 1, "L68")), None);
+        cache.put(&key(1, 1, "L68"), "3").unwrap();
+
+        assert_eq!(cache.get(&key(1, 1, "L68")), Some("3".to_string()));
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_cache_misses_on_changed_input_part_or_version() {
+        let cache = temp_cache("misses");
+        cache.put(&key(1, 1, "L68"), "3").unwrap();
+
+        assert_eq!(cache.get(&key(1, 1, "L69")), None);
+        assert_eq!(cache.get(&key(2, 1, "L68")), None);
+        assert_eq!(cache.get(&key(1, 2, "L68")), None);
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_cache_clear_removes_answers() {
+        let cache = temp_cache("clear");
+        cache.put(&key(1, 1, "L68"), "3").unwrap();
+
+        cache.clear().unwrap();
+
+        assert_eq!(cache.get(&key(1, 1, "L68")), None);
+        cache.clear().unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+/// A problem found while validating puzzle input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// 1-based line number, or 0 when the problem concerns the input as a whole
+    pub line: usize,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(line: usize, message: impl fmt::Display) -> Self {
+        Problem {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+/// Iterate over the non-empty lines of the input together with their 1-based line numbers
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+/// Check that the input is a non-empty rectangular grid made only of `allowed` characters
+pub fn check_grid(input: &str, allowed: &[char]) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut width: Option<usize> = None;
+
+    for (line_no, line) in numbered_lines(input) {
+        if let Some(c) = line.chars().find(|c| !allowed.contains(c)) {
+            problems.push(Problem::new(
+                line_no,
+                format!("unexpected character '{}'", c),
+            ));
+        }
+
+        let len = line.chars().count();
+        match width {
+            None => width = Some(len),
+            Some(w) if w != len => problems.push(Problem::new(
+                line_no,
+                format!("row has {} columns, expected {}", len, w),
+            )),
+            _ => {}
+        }
+    }
+
+    if width.is_none() {
+        problems.push(Problem::new(0, "input is empty"));
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbered_lines_skips_empty_lines_but_keeps_numbering() {
+        let input = "a\n\nb\n";
+
+        assert_eq!(
+            numbered_lines(input).collect::<Vec<_>>(),
+            vec![(1, "a"), (3, "b")]
+        );
+    }
+
+    #[test]
+    fn test_check_grid_accepts_rectangular_grid() {
+        let input = "@.@\n.@.\n";
+
+        assert_eq!(check_grid(input, &['@', '.']), vec![]);
+    }
+
+    #[test]
+    fn test_check_grid_reports_every_problem() {
+        let input = "@.@\n.x.\n@.\n";
+
+        assert_eq!(
+            check_grid(input, &['@', '.']),
+            vec![
+                Problem::new(2, "unexpected character 'x'"),
+                Problem::new(3, "row has 2 columns, expected 3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_grid_reports_empty_input() {
+        assert_eq!(
+            check_grid("", &['@', '.']),
+            vec![Problem::new(0, "input is empty")]
+        );
+    }
+}
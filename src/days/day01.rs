@@ -0,0 +1,2434 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::slice;
+
+use anyhow::{Context, Result, bail};
+
+use crate::check::{Problem, numbered_lines};
+use crate::solution::{Param, ParamKind, Params, Solution};
+use crate::to_lines;
+use crate::trace;
+use crate::trace::Level;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<String>;
+
+    const VERSION: u32 = 2;
+
+    const PARAMS: &'static [Param] = &[
+        DIAL_LENGTH,
+        DIAL_START,
+        DIAL_TARGET,
+        LOCK_WHEELS,
+        LOCK_CARRY,
+    ];
+
+    const EXPORTS: &'static [&'static str] = &[
+        "zero-events",
+        "lock",
+        "lock-alignments",
+        "histogram",
+        "start-counts",
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(to_lines(input))
+    }
+
+    fn part1(lines: &Self::Input) -> Result<String> {
+        solve_part1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<String> {
+        solve_part2(lines)
+    }
+
+    fn part1_with(lines: &Self::Input, params: &Params) -> Result<String> {
+        let dial = turn_dial(lines, Dial::from_params(params)?)?;
+        Ok(dial.landings().to_string())
+    }
+
+    fn part2_with(lines: &Self::Input, params: &Params) -> Result<String> {
+        let dial = turn_dial(lines, Dial::from_params(params)?)?;
+        Ok(dial.passes().to_string())
+    }
+
+    fn inspect(lines: &Self::Input, params: &Params, limit: usize) -> Result<Vec<String>> {
+        dial_states(lines, Dial::from_params(params)?, limit)
+    }
+
+    fn export(lines: &Self::Input, name: &str, params: &Params) -> Result<String> {
+        match name {
+            "zero-events" => zero_events_csv(lines, Dial::from_params(params)?),
+            "lock" => Ok(lock_wheels_csv(&run_lock(lines, params)?)),
+            "lock-alignments" => Ok(lock_alignments_csv(&run_lock(lines, params)?)),
+            "histogram" => {
+                let histogram =
+                    position_histogram(lines, params.get(&DIAL_LENGTH), params.get(&DIAL_START))?;
+                Ok(counts_csv(
+                    "position",
+                    &histogram.landings,
+                    &histogram.passes,
+                ))
+            }
+            "start-counts" => {
+                let counts =
+                    StartCounts::new(lines, params.get(&DIAL_LENGTH), params.get(&DIAL_TARGET))?;
+                Ok(counts_csv("start", &counts.landings, &counts.passes))
+            }
+            _ => bail!("Unknown export '{}'", name),
+        }
+    }
+
+    fn check(input: &str) -> Vec<Problem> {
+        numbered_lines(input)
+            .filter_map(|(line_no, line)| {
+                parse_line(line)
+                    .err()
+                    .map(|err| Problem::new(line_no, format!("{:#}", err)))
+            })
+            .collect()
+    }
+}
+
+const DIAL_LENGTH: Param = Param {
+    name: "dial_length",
+    default: 100,
+    description: "Number of positions on the dial, or on wheel n of the lock as dial_length.n",
+    kind: ParamKind::PerItem,
+};
+
+const DIAL_START: Param = Param {
+    name: "dial_start",
+    default: 50,
+    description: "Position the dial starts at, or wheel n of the lock as dial_start.n",
+    kind: ParamKind::PerItem,
+};
+
+const DIAL_TARGET: Param = Param {
+    name: "dial_target",
+    default: 0,
+    description: "Position whose landings and passes are counted, or those of wheel n of the \
+                  lock as dial_target.n",
+    kind: ParamKind::PerItem,
+};
+
+const LOCK_WHEELS: Param = Param {
+    name: "wheels",
+    default: 1,
+    description: "Number of wheels of the lock exports",
+    kind: ParamKind::Number,
+};
+
+const LOCK_CARRY: Param = Param {
+    name: "carry",
+    default: 0,
+    description: "Whether the clicks of each wheel past 0 also turn the next, like an odometer",
+    kind: ParamKind::Flag,
+};
+
+pub fn solve_part1(lines: &[String]) -> Result<String> {
+    let dial = turn_dial(lines, Dial::from_params(&Params::default())?)?;
+    Ok(dial.landings().to_string())
+}
+
+pub fn solve_part2(lines: &[String]) -> Result<String> {
+    let dial = turn_dial(lines, Dial::from_params(&Params::default())?)?;
+    Ok(dial.passes().to_string())
+}
+
+/// Apply the commands of every line to `dial`
+fn turn_dial(lines: &[String], mut dial: Dial) -> Result<Dial> {
+    let program = parse_program(lines)?;
+    dial.run(&program)?;
+
+    Ok(dial)
+}
+
+/// CSV of what each line did to `dial`: the positions it started and ended at, how often it
+/// passed the target and how often it landed on it
+fn zero_events_csv(lines: &[String], mut dial: Dial) -> Result<String> {
+    let mut csv = String::from("line,commands,start,end,passes,landings\n");
+    for (line_no, line) in lines.iter().enumerate() {
+        let (start, landings, passes) = (dial.position, dial.landings, dial.passes);
+        dial.run(&parse_line(line)?)?;
+        csv += &format!(
+            "{},{},{},{},{},{}\n",
+            line_no + 1,
+            csv_field(line),
+            start,
+            dial.position,
+            dial.passes - passes,
+            dial.landings - landings
+        );
+    }
+
+    Ok(csv)
+}
+
+/// State of `dial` after each of the first `limit` commands
+fn dial_states(lines: &[String], mut dial: Dial, limit: usize) -> Result<Vec<String>> {
+    let program = parse_program(lines)?;
+    let mut states = vec![];
+
+    for step in commands(&program).take(limit) {
+        match dial_command(&step)? {
+            Command::Rotate(cmd) => dial.rotate(&cmd)?,
+            Command::Set(position) => dial.set(position)?,
+        }
+        states.push(format!(
+            "{}: dial at {}, {} landings, {} passes",
+            step, dial.position, dial.landings, dial.passes
+        ));
+    }
+
+    Ok(states)
+}
+
+/// Run every line through the lock described by `params`
+fn run_lock(lines: &[String], params: &Params) -> Result<Lock> {
+    let mut lock = Lock::from_params(params)?;
+    lock.run(lines)?;
+
+    Ok(lock)
+}
+
+/// CSV of where every wheel of `lock` ended and how often it landed on and passed its target,
+/// along with the number of commands after which every wheel was on its target
+fn lock_wheels_csv(lock: &Lock) -> String {
+    let mut csv = String::from("wheel,length,target,end,landings,passes,lock_alignments\n");
+    for (i, wheel) in lock.wheels().iter().enumerate() {
+        csv += &format!(
+            "{},{},{},{},{},{},{}\n",
+            i + 1,
+            wheel.length,
+            wheel.target,
+            wheel.position,
+            wheel.landings,
+            wheel.passes,
+            lock.alignments()
+        );
+    }
+
+    csv
+}
+
+/// CSV of the landings and passes for every position, `key` naming what the position is
+fn counts_csv(key: &str, landings: &[usize], passes: &[usize]) -> String {
+    let mut csv = format!("{},landings,passes\n", key);
+    for (position, (landings, passes)) in landings.iter().zip(passes).enumerate() {
+        csv += &format!("{},{},{}\n", position, landings, passes);
+    }
+
+    csv
+}
+
+/// CSV of the first commands after which every wheel of `lock` was on its target
+fn lock_alignments_csv(lock: &Lock) -> String {
+    let mut csv = String::from("command\n");
+    for step in lock.first_alignments() {
+        csv += &format!("{}\n", step);
+    }
+
+    csv
+}
+
+/// `field` quoted if it contains a comma or a quote
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Command of a step for a single dial, which only has wheel 1
+fn dial_command(step: &Step) -> Result<Command> {
+    if let Some(wheel) = step.wheel
+        && wheel != 1
+    {
+        bail!("Wheel {} doesn't exist, the dial has a single wheel", wheel);
+    }
+
+    Ok(step.command)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Right,
+    Left,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RotationCmd {
+    direction: Direction,
+    amount: usize,
+}
+
+impl RotationCmd {
+    /// Offsets from the target, `(position - target) % length`, from which the clicks of this
+    /// rotation left over after its full turns reach the target, and the offset from which
+    /// the rotation ends on it
+    fn reaching(&self, length: usize) -> (Range<usize>, usize) {
+        let remainder = self.amount % length;
+        match self.direction {
+            Direction::Right => ((length - remainder)..length, (length - remainder) % length),
+            Direction::Left => (1..remainder + 1, remainder),
+        }
+    }
+}
+
+impl fmt::Display for RotationCmd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.direction {
+            Direction::Right => write!(f, "R{}", self.amount),
+            Direction::Left => write!(f, "L{}", self.amount),
+        }
+    }
+}
+
+/// A single step of a scenario
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Rotate(RotationCmd),
+    /// Move the dial to a position without clicking, `=N`
+    Set(usize),
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Rotate(cmd) => write!(f, "{}", cmd),
+            Command::Set(position) => write!(f, "={}", position),
+        }
+    }
+}
+
+/// A command for every wheel of a lock, or only the wheel numbered from 1 before it, `2R15`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Step {
+    wheel: Option<usize>,
+    command: Command,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(wheel) = self.wheel {
+            write!(f, "{}", wheel)?;
+        }
+        write!(f, "{}", self.command)
+    }
+}
+
+/// A step or a group of them repeated a number of times, `(R5 L3)x100`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Step(Step),
+    Repeat(Vec<Item>, usize),
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::Step(step) => write!(f, "{}", step),
+            Item::Repeat(items, count) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")x{}", count)
+            }
+        }
+    }
+}
+
+/// Deepest nesting of repeat groups accepted, keeping the parser's recursion bounded
+const MAX_GROUP_DEPTH: usize = 32;
+
+/// Parse every line into a single scenario
+fn parse_program(lines: &[String]) -> Result<Vec<Item>> {
+    let mut program = vec![];
+    for line in lines {
+        program.extend(parse_line(line)?);
+    }
+
+    Ok(program)
+}
+
+/// Parse a line of commands separated by commas or spaces, e.g. `R5, (L3 =20)x4 # comment`.
+/// Besides the puzzle's `R<n>` and `L<n>`, `=<n>` moves the dial to position `n` without
+/// counting a landing or a pass, `( ... )x<n>` repeats its commands `n` times and `#` starts
+/// a comment running to the end of the line. A wheel number before a command, `2R15`,
+/// addresses a single wheel of a `Lock`.
+fn parse_line(line: &str) -> Result<Vec<Item>> {
+    if line.trim().is_empty() {
+        bail!("Should have a command or a comment in line: {:?}", line);
+    }
+
+    let mut parser = LineParser { line, pos: 0 };
+    let items = parser.items(0)?;
+    match parser.peek() {
+        None | Some('#') => Ok(items),
+        Some(_) => bail!(
+            "Unmatched ')' at column {} in line: {}",
+            parser.pos + 1,
+            line
+        ),
+    }
+}
+
+struct LineParser<'a> {
+    line: &'a str,
+    /// Byte offset of the next character
+    pos: usize,
+}
+
+impl LineParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.line[self.pos..].chars().next()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(c) = self.peek()
+            && (c == ',' || c.is_whitespace())
+        {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// Items up to the end of the line, a comment or the `)` closing the current group
+    fn items(&mut self, depth: usize) -> Result<Vec<Item>> {
+        let mut items = vec![];
+
+        loop {
+            self.skip_separators();
+            match self.peek() {
+                None | Some('#') | Some(')') => return Ok(items),
+                Some('(') => items.extend(self.group(depth + 1)?),
+                Some(_) => items.push(Item::Step(self.step()?)),
+            }
+        }
+    }
+
+    /// A repeat group, `None` when it has nothing to repeat
+    fn group(&mut self, depth: usize) -> Result<Option<Item>> {
+        let column = self.pos + 1;
+        if depth > MAX_GROUP_DEPTH {
+            bail!(
+                "Groups should be nested at most {} deep, at column {} in line: {}",
+                MAX_GROUP_DEPTH,
+                column,
+                self.line
+            );
+        }
+
+        self.pos += 1;
+        let items = self.items(depth)?;
+        if self.peek() != Some(')') {
+            bail!("Unclosed '(' at column {} in line: {}", column, self.line);
+        }
+        self.pos += 1;
+        if self.peek() != Some('x') {
+            bail!(
+                "Should have a repeat count like 'x3' at column {} in line: {}",
+                self.pos + 1,
+                self.line
+            );
+        }
+        self.pos += 1;
+        let count = self.number()?;
+
+        // Dropping empty groups keeps expansion from spinning on repeats of nothing
+        Ok((!items.is_empty() && count > 0).then_some(Item::Repeat(items, count)))
+    }
+
+    fn step(&mut self) -> Result<Step> {
+        let wheel = match self.peek() {
+            Some(c) if c.is_ascii_digit() => {
+                let column = self.pos + 1;
+                let wheel = self.number()?;
+                if wheel == 0 {
+                    bail!(
+                        "Wheels are numbered from 1, got 0 at column {} in line: {}",
+                        column,
+                        self.line
+                    );
+                }
+                Some(wheel)
+            }
+            _ => None,
+        };
+
+        Ok(Step {
+            wheel,
+            command: self.command()?,
+        })
+    }
+
+    fn command(&mut self) -> Result<Command> {
+        let column = self.pos + 1;
+        let Some(kind) = self.peek() else {
+            bail!(
+                "Should have a command at column {} in line: {}",
+                column,
+                self.line
+            );
+        };
+        self.pos += kind.len_utf8();
+
+        match kind {
+            'R' => Ok(Command::Rotate(RotationCmd {
+                direction: Direction::Right,
+                amount: self.number()?,
+            })),
+            'L' => Ok(Command::Rotate(RotationCmd {
+                direction: Direction::Left,
+                amount: self.number()?,
+            })),
+            '=' => Ok(Command::Set(self.number()?)),
+            _ => bail!(
+                "Unknown command '{}' at column {} in line: {}",
+                kind,
+                column,
+                self.line
+            ),
+        }
+    }
+
+    fn number(&mut self) -> Result<usize> {
+        let start = self.pos;
+        let digits = self.line[start..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        self.pos += digits;
+
+        if digits == 0 {
+            bail!(
+                "Should have a number at column {} in line: {}",
+                start + 1,
+                self.line
+            );
+        }
+        self.line[start..self.pos].parse().with_context(|| {
+            format!(
+                "Should be able to parse number at column {} in line: {}",
+                start + 1,
+                self.line
+            )
+        })
+    }
+}
+
+/// Whether a scenario has an `=N` command anywhere
+fn contains_set(program: &[Item]) -> bool {
+    program.iter().any(|item| match item {
+        Item::Step(step) => matches!(step.command, Command::Set(_)),
+        Item::Repeat(items, _) => contains_set(items),
+    })
+}
+
+/// Number of commands of a scenario once its repeat groups are expanded, `None` on overflow
+fn command_count(program: &[Item]) -> Option<usize> {
+    program.iter().try_fold(0usize, |count, item| {
+        let item_count = match item {
+            Item::Step(_) => 1,
+            Item::Repeat(items, repeats) => command_count(items)?.checked_mul(*repeats)?,
+        };
+        count.checked_add(item_count)
+    })
+}
+
+/// Commands of a scenario in order, expanding repeat groups as they are reached
+/// so that large repeat counts take no memory
+fn commands(program: &[Item]) -> Commands<'_> {
+    Commands {
+        stack: vec![Frame {
+            items: program,
+            next: 0,
+            remaining: 1,
+        }],
+    }
+}
+
+struct Commands<'a> {
+    stack: Vec<Frame<'a>>,
+}
+
+/// Progress through one level of nested groups
+struct Frame<'a> {
+    items: &'a [Item],
+    next: usize,
+    /// Repetitions left, the current one included
+    remaining: usize,
+}
+
+impl Iterator for Commands<'_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(item) = frame.items.get(frame.next) else {
+                if frame.remaining > 1 {
+                    frame.remaining -= 1;
+                    frame.next = 0;
+                } else {
+                    self.stack.pop();
+                }
+                continue;
+            };
+            frame.next += 1;
+
+            match item {
+                Item::Step(step) => return Some(*step),
+                Item::Repeat(items, count) => self.stack.push(Frame {
+                    items,
+                    next: 0,
+                    remaining: *count,
+                }),
+            }
+        }
+    }
+}
+
+/// What a run of commands does to a dial from the position it starts at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Effect {
+    end: usize,
+    landings: usize,
+    passes: usize,
+}
+
+impl Effect {
+    /// Effect of this run followed by `next`
+    fn then(&self, next: &Effect) -> Result<Self> {
+        Ok(Effect {
+            end: next.end,
+            landings: self
+                .landings
+                .checked_add(next.landings)
+                .context("Landing count overflowed")?,
+            passes: self
+                .passes
+                .checked_add(next.passes)
+                .context("Pass count overflowed")?,
+        })
+    }
+
+    /// Effect of `times` runs of a cycle, which ends where it started
+    fn cycled(&self, times: usize) -> Result<Self> {
+        Ok(Effect {
+            end: self.end,
+            landings: self
+                .landings
+                .checked_mul(times)
+                .context("Landing count overflowed")?,
+            passes: self
+                .passes
+                .checked_mul(times)
+                .context("Pass count overflowed")?,
+        })
+    }
+}
+
+/// Effects of a single repetition of a group's block by the position it starts at, keyed by
+/// the address of the block
+type Memo = HashMap<(*const Item, usize), Effect>;
+
+/// A dial numbered `0..length` that counts how often it points at its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    length: usize,
+    target: usize,
+    position: usize,
+    /// Rotations that ended at the target (part 1)
+    landings: usize,
+    /// Clicks that ended at the target, during or at the end of a rotation (part 2)
+    passes: usize,
+}
+
+impl Dial {
+    pub fn new(length: usize, start: usize, target: usize) -> Result<Self> {
+        if length == 0 {
+            bail!("Dial length should be positive");
+        }
+        if start >= length || target >= length {
+            bail!(
+                "Dial start {} and target {} should be less than the length {}",
+                start,
+                target,
+                length
+            );
+        }
+
+        Ok(Dial {
+            length,
+            target,
+            position: start,
+            landings: 0,
+            passes: 0,
+        })
+    }
+
+    /// Dial configured by the day's parameters
+    fn from_params(params: &Params) -> Result<Self> {
+        Dial::new(
+            params.get(&DIAL_LENGTH),
+            params.get(&DIAL_START),
+            params.get(&DIAL_TARGET),
+        )
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn landings(&self) -> usize {
+        self.landings
+    }
+
+    pub fn passes(&self) -> usize {
+        self.passes
+    }
+
+    /// Apply every command of `program`. Repeat groups are worked out from the positions
+    /// their repetitions start at rather than one repetition after another, so that their
+    /// counts don't matter however large they are.
+    fn run(&mut self, program: &[Item]) -> Result<()> {
+        let mut memo = Memo::new();
+
+        for item in program {
+            let effect = self.effect(slice::from_ref(item), self.position, &mut memo)?;
+            let total = Effect {
+                end: self.position,
+                landings: self.landings,
+                passes: self.passes,
+            }
+            .then(&effect)?;
+            (self.position, self.landings, self.passes) = (total.end, total.landings, total.passes);
+            trace!(
+                Level::Debug,
+                "{}: dial at {}, landed on target {} times, passed it {} times",
+                item,
+                self.position,
+                self.landings,
+                self.passes
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Effect of `items` on this dial from `start`
+    fn effect(&self, items: &[Item], start: usize, memo: &mut Memo) -> Result<Effect> {
+        let mut total = Effect {
+            end: start,
+            landings: 0,
+            passes: 0,
+        };
+
+        for item in items {
+            let effect = match item {
+                Item::Step(step) => {
+                    let mut dial = Dial {
+                        position: total.end,
+                        landings: 0,
+                        passes: 0,
+                        ..*self
+                    };
+                    match dial_command(step)? {
+                        Command::Rotate(cmd) => dial.rotate(&cmd)?,
+                        Command::Set(position) => dial.set(position)?,
+                    }
+                    Effect {
+                        end: dial.position,
+                        landings: dial.landings,
+                        passes: dial.passes,
+                    }
+                }
+                Item::Repeat(block, count) => self.repeat(block, *count, total.end, memo)?,
+            };
+            total = total.then(&effect)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Effect of `count` repetitions of `block` from `start`
+    fn repeat(
+        &self,
+        block: &[Item],
+        count: usize,
+        start: usize,
+        memo: &mut Memo,
+    ) -> Result<Effect> {
+        if let Some(rotations) = flat_rotations(block)? {
+            return self.repeat_rotations(&rotations, count, start);
+        }
+
+        // A repetition only depends on the position it starts at, so once a start comes back
+        // the repetitions since its first time keep cycling, which takes at most `length`
+        // repetitions to find
+        let mut firsts = HashMap::new();
+        let mut effects = vec![];
+        let mut position = start;
+        while effects.len() < count {
+            if let Some(&first) = firsts.get(&position) {
+                let cycle = chain(position, &effects[first..])?;
+                let remaining = count - effects.len();
+                let rest = remaining % (effects.len() - first);
+                return chain(start, &effects)?
+                    .then(&cycle.cycled(remaining / (effects.len() - first))?)?
+                    .then(&chain(position, &effects[first..first + rest])?);
+            }
+            firsts.insert(position, effects.len());
+
+            let effect = match memo.get(&(block.as_ptr(), position)) {
+                Some(&effect) => effect,
+                None => {
+                    let effect = self.effect(block, position, memo)?;
+                    memo.insert((block.as_ptr(), position), effect);
+                    effect
+                }
+            };
+            effects.push(effect);
+            position = effect.end;
+        }
+
+        chain(start, &effects)
+    }
+
+    /// Effect of `count` repetitions of `rotations` from `start`, in `O(rotations * log
+    /// length)` time. Each repetition starts `shift` positions after the one before it, so a
+    /// rotation passes or lands on the target in as many repetitions as there are `j` for
+    /// which the position it starts at, `(offset + j * shift) % length` relative to the
+    /// target, falls in some range, which `count_at_least` counts in closed form.
+    fn repeat_rotations(
+        &self,
+        rotations: &[RotationCmd],
+        count: usize,
+        start: usize,
+    ) -> Result<Effect> {
+        let length = self.length;
+        let shift = rotations
+            .iter()
+            .fold(0, |position, cmd| turned(position, cmd, length));
+        let at_least = |offset, threshold| count_at_least(count, length, offset, shift, threshold);
+        let mut offset = sub_mod(start, self.target, length);
+        let (mut landings, mut passes) = (0u128, 0u128);
+
+        for cmd in rotations {
+            let (reaching, landing) = cmd.reaching(length);
+            let full_turns = count as u128 * (cmd.amount / length) as u128;
+            passes = passes
+                .checked_add(
+                    full_turns + at_least(offset, reaching.start) - at_least(offset, reaching.end),
+                )
+                .context("Pass count overflowed")?;
+            landings += at_least(offset, landing) - at_least(offset, landing + 1);
+            offset = turned(offset, cmd, length);
+        }
+
+        let turns = (count % length) as u128 * shift as u128 % length as u128;
+        Ok(Effect {
+            end: add_mod(start, turns as usize, length),
+            landings: landings.try_into().context("Landing count overflowed")?,
+            passes: passes.try_into().context("Pass count overflowed")?,
+        })
+    }
+
+    /// Clicks between the last position and 0 while rotating by `cmd`, whatever the target
+    fn wraps(&self, cmd: &RotationCmd) -> Result<usize> {
+        // Going right these are the clicks onto 0, going left those onto the last position
+        let target = match cmd.direction {
+            Direction::Right => 0,
+            Direction::Left => self.length - 1,
+        };
+        let mut probe = Dial {
+            target,
+            passes: 0,
+            ..*self
+        };
+        probe.rotate(cmd)?;
+
+        Ok(probe.passes)
+    }
+
+    /// Move to `position` without counting a landing or a pass
+    fn set(&mut self, position: usize) -> Result<()> {
+        if position >= self.length {
+            bail!(
+                "Position {} should be less than the dial length {}",
+                position,
+                self.length
+            );
+        }
+        self.position = position;
+
+        Ok(())
+    }
+
+    /// Position the dial ends at after rotating by `cmd` from its current position
+    fn turned(&self, cmd: &RotationCmd) -> usize {
+        turned(self.position, cmd, self.length)
+    }
+
+    fn rotate(&mut self, cmd: &RotationCmd) -> Result<()> {
+        let start = self.position;
+        self.position = self.turned(cmd);
+
+        // Clicks needed to reach the target from the start, a full turn when already on it
+        let to_target = match cmd.direction {
+            Direction::Right => sub_mod(self.target, start, self.length),
+            Direction::Left => sub_mod(start, self.target, self.length),
+        };
+        let to_target = if to_target == 0 {
+            self.length
+        } else {
+            to_target
+        };
+
+        // After reaching the target once, the dial passes it again every full turn
+        if cmd.amount >= to_target {
+            self.passes = self
+                .passes
+                .checked_add((cmd.amount - to_target) / self.length + 1)
+                .context("Pass count overflowed")?;
+        }
+        if self.position == self.target {
+            self.landings += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Position a dial of `length` positions ends at after rotating by `cmd` from `position`
+fn turned(position: usize, cmd: &RotationCmd, length: usize) -> usize {
+    // Reduce the amount first so that huge rotations can't overflow
+    let remainder = cmd.amount % length;
+
+    match cmd.direction {
+        Direction::Right => add_mod(position, remainder, length),
+        Direction::Left => sub_mod(position, remainder, length),
+    }
+}
+
+/// Effect of `effects` run one after another from `start`
+fn chain(start: usize, effects: &[Effect]) -> Result<Effect> {
+    effects.iter().try_fold(
+        Effect {
+            end: start,
+            landings: 0,
+            passes: 0,
+        },
+        |total, effect| total.then(effect),
+    )
+}
+
+/// Rotations of a block without nested groups or `=N` commands, `None` for other blocks
+fn flat_rotations(block: &[Item]) -> Result<Option<Vec<RotationCmd>>> {
+    let mut rotations = vec![];
+    for item in block {
+        let Item::Step(step) = item else {
+            return Ok(None);
+        };
+        match dial_command(step)? {
+            Command::Rotate(cmd) => rotations.push(cmd),
+            Command::Set(_) => return Ok(None),
+        }
+    }
+
+    Ok(Some(rotations))
+}
+
+/// Number of `j < n` for which `(offset + j * shift) % length >= threshold`, for `offset`
+/// and `shift` below `length` and `threshold` at most `length`
+fn count_at_least(n: usize, length: usize, offset: usize, shift: usize, threshold: usize) -> u128 {
+    // `[x % length >= threshold]` is `(x + length - threshold) / length - x / length`
+    let (n, m, a, b) = (n as u128, length as u128, shift as u128, offset as u128);
+    floor_sum(n, m, a, b + m - threshold as u128).wrapping_sub(floor_sum(n, m, a, b))
+}
+
+/// Sum of `(a * j + b) / m` over `j < n`, for `n` and `m` that fit in a `u64`, in `O(log m)`
+/// steps. The sum may wrap around, which differences of two of them undo.
+fn floor_sum(mut n: u128, mut m: u128, mut a: u128, mut b: u128) -> u128 {
+    let mut sum: u128 = 0;
+    loop {
+        if a >= m {
+            sum = sum.wrapping_add((n * n.saturating_sub(1) / 2).wrapping_mul(a / m));
+            a %= m;
+        }
+        if b >= m {
+            sum = sum.wrapping_add(n.wrapping_mul(b / m));
+            b %= m;
+        }
+        // The rest counts lattice points under the line, swapped around to count them
+        // along the other axis
+        let top = a * n + b;
+        if top < m {
+            return sum;
+        }
+        (n, b) = (top / m, top % m);
+        (m, a) = (a, m);
+    }
+}
+
+/// `(a + b) % m` for `a` and `b` below `m`, without overflowing however large `m` is
+fn add_mod(a: usize, b: usize, m: usize) -> usize {
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `(a - b) % m` for `a` and `b` below `m`, kept positive
+fn sub_mod(a: usize, b: usize, m: usize) -> usize {
+    if a >= b { a - b } else { a + (m - b) }
+}
+
+/// Most steps after which every wheel was on its target a `Lock` lists, beyond which it
+/// only counts them
+const MAX_LISTED_ALIGNMENTS: usize = 1000;
+
+/// Combination lock of several dials, each counting its own target. A command without a wheel
+/// number turns every wheel by the same amount. With carry, every click of a wheel between
+/// its last position and 0 also turns the next wheel a click in the same direction, like an
+/// odometer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock {
+    wheels: Vec<Dial>,
+    carry: bool,
+    /// Commands applied so far
+    steps: usize,
+    /// Number of steps after which every wheel was on its target
+    alignments: usize,
+    /// The first `MAX_LISTED_ALIGNMENTS` of those steps
+    first_alignments: Vec<usize>,
+}
+
+impl Lock {
+    pub fn new(wheels: Vec<Dial>, carry: bool) -> Result<Self> {
+        if wheels.is_empty() {
+            bail!("Lock should have at least one wheel");
+        }
+
+        Ok(Lock {
+            wheels,
+            carry,
+            steps: 0,
+            alignments: 0,
+            first_alignments: vec![],
+        })
+    }
+
+    /// Lock of `wheels` dials, wheel `n` configured like the dial unless its length, start or
+    /// target is set for it alone
+    fn from_params(params: &Params) -> Result<Self> {
+        let wheels = (1..=params.get(&LOCK_WHEELS))
+            .map(|n| {
+                Dial::new(
+                    params.get_nth(&DIAL_LENGTH, n),
+                    params.get_nth(&DIAL_START, n),
+                    params.get_nth(&DIAL_TARGET, n),
+                )
+                .with_context(|| format!("Wheel {} of the lock", n))
+            })
+            .collect::<Result<_>>()?;
+
+        Lock::new(wheels, params.flag(&LOCK_CARRY))
+    }
+
+    pub fn wheels(&self) -> &[Dial] {
+        &self.wheels
+    }
+
+    /// Number of commands after which every wheel was on its target
+    pub fn alignments(&self) -> usize {
+        self.alignments
+    }
+
+    /// Numbers of the first `MAX_LISTED_ALIGNMENTS` commands, counted from 1, after which
+    /// every wheel was on its target
+    pub fn first_alignments(&self) -> &[usize] {
+        &self.first_alignments
+    }
+
+    /// Apply the commands of every line
+    pub fn run(&mut self, lines: &[String]) -> Result<()> {
+        let program = parse_program(lines)?;
+
+        for step in commands(&program) {
+            self.apply(&step)?;
+            trace!(
+                Level::Debug,
+                "{}: wheels at {:?}",
+                step,
+                self.wheels.iter().map(Dial::position).collect::<Vec<_>>()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn apply(&mut self, step: &Step) -> Result<()> {
+        let addressed = match step.wheel {
+            None => 0..self.wheels.len(),
+            Some(wheel) if wheel <= self.wheels.len() => wheel - 1..wheel,
+            Some(wheel) => bail!(
+                "Wheel {} doesn't exist, the lock has {} wheels",
+                wheel,
+                self.wheels.len()
+            ),
+        };
+
+        match step.command {
+            Command::Set(position) => {
+                for wheel in &mut self.wheels[addressed] {
+                    wheel.set(position)?;
+                }
+            }
+            Command::Rotate(cmd) => {
+                let mut carried = 0;
+                for i in addressed.start..self.wheels.len() {
+                    let amount = if addressed.contains(&i) {
+                        cmd.amount
+                            .checked_add(carried)
+                            .context("Rotation with carry should fit in a usize")?
+                    } else if carried > 0 {
+                        carried
+                    } else {
+                        break;
+                    };
+                    let cmd = RotationCmd { amount, ..cmd };
+
+                    carried = if self.carry {
+                        self.wheels[i].wraps(&cmd)?
+                    } else {
+                        0
+                    };
+                    self.wheels[i].rotate(&cmd)?;
+                }
+            }
+        }
+
+        self.steps += 1;
+        if self
+            .wheels
+            .iter()
+            .all(|wheel| wheel.position == wheel.target)
+        {
+            self.alignments += 1;
+            if self.first_alignments.len() < MAX_LISTED_ALIGNMENTS {
+                self.first_alignments.push(self.steps);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// How often every position of a dial was landed on and passed by a list of rotations.
+/// The counts of a position are those of a `Dial` targeting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// Rotations that ended at each position
+    pub landings: Vec<usize>,
+    /// Clicks that ended at each position, the ends of rotations included
+    pub passes: Vec<usize>,
+}
+
+impl Histogram {
+    /// Position landed on most often and its count, the lowest such position on ties
+    pub fn most_landed(&self) -> (usize, usize) {
+        most_common(&self.landings)
+    }
+
+    /// Position passed most often and its count, the lowest such position on ties
+    pub fn most_passed(&self) -> (usize, usize) {
+        most_common(&self.passes)
+    }
+}
+
+fn most_common(counts: &[usize]) -> (usize, usize) {
+    counts
+        .iter()
+        .copied()
+        .enumerate()
+        .fold((0, 0), |best, (position, count)| {
+            if count > best.1 {
+                (position, count)
+            } else {
+                best
+            }
+        })
+}
+
+fn least_common(counts: &[usize]) -> (usize, usize) {
+    counts
+        .iter()
+        .copied()
+        .enumerate()
+        .fold((0, usize::MAX), |best, (position, count)| {
+            if count < best.1 {
+                (position, count)
+            } else {
+                best
+            }
+        })
+}
+
+/// Longest dial a `Histogram`, which has counts for every position, is computed for
+const MAX_HISTOGRAM_LENGTH: usize = 1 << 20;
+
+/// Count the landings and passes of every position in one pass over the commands,
+/// in `O(commands + length)` time however large the rotations are
+pub fn position_histogram(lines: &[String], length: usize, start: usize) -> Result<Histogram> {
+    histogram_of(&parse_program(lines)?, length, start)
+}
+
+fn histogram_of(program: &[Item], length: usize, start: usize) -> Result<Histogram> {
+    let mut dial = Dial::new(length, start, 0)?;
+    if length > MAX_HISTOGRAM_LENGTH {
+        bail!(
+            "Dial length {} is over the limit of {} positions for histograms, which count every position",
+            length,
+            MAX_HISTOGRAM_LENGTH
+        );
+    }
+    let mut landings = vec![0; length];
+    // Full turns pass every position, the remaining clicks of each rotation pass an arc of
+    // consecutive positions, added to a difference array to keep each rotation O(1)
+    let mut full_turns: usize = 0;
+    let mut arcs: Vec<isize> = vec![0; length + 1];
+
+    for step in commands(program) {
+        let cmd = match dial_command(&step)? {
+            Command::Rotate(cmd) => cmd,
+            Command::Set(position) => {
+                dial.set(position)?;
+                continue;
+            }
+        };
+        let remainder = cmd.amount % length;
+        let first = match cmd.direction {
+            Direction::Right => add_mod(dial.position, 1 % length, length),
+            Direction::Left => sub_mod(dial.position, remainder, length),
+        };
+        full_turns = full_turns
+            .checked_add(cmd.amount / length)
+            .context("Pass count overflowed")?;
+        if remainder <= length - first {
+            arcs[first] += 1;
+            arcs[first + remainder] -= 1;
+        } else {
+            arcs[first] += 1;
+            arcs[length] -= 1;
+            arcs[0] += 1;
+            arcs[remainder - (length - first)] -= 1;
+        }
+
+        dial.position = dial.turned(&cmd);
+        landings[dial.position] += 1;
+    }
+
+    let mut arc_count: isize = 0;
+    let passes = arcs[..length]
+        .iter()
+        .map(|delta| {
+            arc_count += delta;
+            full_turns
+                .checked_add(arc_count as usize)
+                .context("Pass count overflowed")
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Histogram { landings, passes })
+}
+
+/// Landings on and passes of a fixed target for every position the dial could start at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartCounts {
+    /// Rotations that ended at the target, indexed by start position (part 1)
+    pub landings: Vec<usize>,
+    /// Clicks that ended at the target, indexed by start position (part 2)
+    pub passes: Vec<usize>,
+}
+
+impl StartCounts {
+    /// Count for every start at once from a single histogram: turning the start by `d`
+    /// turns every later position by `d`, so starting at `s` and targeting `t` counts the
+    /// same as starting at 0 and targeting `t - s`
+    pub fn new(lines: &[String], length: usize, target: usize) -> Result<Self> {
+        Dial::new(length, 0, target)?;
+        let program = parse_program(lines)?;
+        if contains_set(&program) {
+            bail!(
+                "Start counts don't support `=N` commands, after which the start no longer matters"
+            );
+        }
+        let histogram = histogram_of(&program, length, 0)?;
+        let shifted = |counts: &[usize]| {
+            (0..length)
+                .map(|start| counts[sub_mod(target, start, length)])
+                .collect()
+        };
+
+        Ok(StartCounts {
+            landings: shifted(&histogram.landings),
+            passes: shifted(&histogram.passes),
+        })
+    }
+
+    fn counts(&self, part: u8) -> Result<&[usize]> {
+        match part {
+            1 => Ok(&self.landings),
+            2 => Ok(&self.passes),
+            _ => bail!("Part should be 1 or 2, got {}", part),
+        }
+    }
+
+    /// Starts for which the part's answer is `count`
+    pub fn starts_with(&self, part: u8, count: usize) -> Result<Vec<usize>> {
+        Ok(self
+            .counts(part)?
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c == count)
+            .map(|(start, _)| start)
+            .collect())
+    }
+
+    /// Start maximising the part's answer and the answer, the lowest such start on ties
+    pub fn max(&self, part: u8) -> Result<(usize, usize)> {
+        Ok(most_common(self.counts(part)?))
+    }
+
+    /// Start minimising the part's answer and the answer, the lowest such start on ties
+    pub fn min(&self, part: u8) -> Result<(usize, usize)> {
+        Ok(least_common(self.counts(part)?))
+    }
+}
+
+/// Most commands a `RotationTree` holds, each taking `O(log commands)` memory
+const MAX_TREE_COMMANDS: usize = 1 << 18;
+
+/// Segment tree over a list of commands, answering how often the target is passed by any
+/// range of consecutive commands from any start position in `O(log² commands)` time, with
+/// memory independent of the dial length.
+///
+/// Between two `=N` commands the dial is at `z + offset` relative to the target before each
+/// command, `offset` being where the commands before it turned the dial to and `z` depending
+/// only on where the range starts. A rotation passes the target its full turns plus once more
+/// for `z` in an arc, which is a sum of `[z >= threshold]` terms, so the passes of a range are
+/// its full turns and constant terms, summed up front, plus the signs of its thresholds up to
+/// `z`, which each node keeps sorted.
+#[derive(Debug, Clone)]
+pub struct RotationTree {
+    length: usize,
+    target: usize,
+    /// Number of commands, once repeat groups are expanded
+    len: usize,
+    /// Number of leaves, the command count rounded up to a power of two
+    size: usize,
+    /// Position of the dial before each command and after the last, relative to where the
+    /// commands started and ignoring `=N` commands
+    offsets: Vec<usize>,
+    /// Full turns and constant terms of the commands before each one and after the last
+    passes: Vec<usize>,
+    /// Index and position of every `=N` command, in order
+    sets: Vec<(usize, usize)>,
+    /// Passes from each `=N` command to the next one, summed over those before each one
+    set_passes: Vec<usize>,
+    /// Thresholds under node `i` in order, each with the sum of the signs up to it. Node `i`
+    /// covers nodes `2 * i` and `2 * i + 1`, leaves start at `size`.
+    nodes: Vec<Vec<(usize, isize)>>,
+}
+
+impl RotationTree {
+    /// Build the tree in `O(commands * log² commands)` time
+    pub fn new(lines: &[String], length: usize, target: usize) -> Result<Self> {
+        let mut dial = Dial::new(length, 0, target)?;
+        let program = parse_program(lines)?;
+        let Some(len) = command_count(&program).filter(|&len| len <= MAX_TREE_COMMANDS) else {
+            bail!(
+                "A rotation tree holds at most {} commands once groups are repeated",
+                MAX_TREE_COMMANDS
+            );
+        };
+        let size = len.next_power_of_two();
+        let mut tree = RotationTree {
+            length,
+            target,
+            len,
+            size,
+            offsets: vec![0],
+            passes: vec![0],
+            sets: vec![],
+            set_passes: vec![0],
+            nodes: vec![vec![]; 2 * size],
+        };
+
+        for (i, step) in commands(&program).enumerate() {
+            let offset = tree.offsets[i];
+            let mut passes = 0;
+            match dial_command(&step)? {
+                Command::Set(position) => {
+                    dial.set(position)?;
+                    tree.sets.push((i, position));
+                    tree.offsets.push(offset);
+                }
+                Command::Rotate(cmd) => {
+                    passes = cmd.amount / length;
+                    let (reaching, _) = cmd.reaching(length);
+                    let leaf = &mut tree.nodes[size + i];
+                    let mut threshold = |threshold: usize, sign| match threshold {
+                        0 => passes += 1,
+                        threshold if threshold < length => leaf.push((threshold, sign)),
+                        _ => {}
+                    };
+                    // The arc of `z` reaching the target, which may wrap past 0
+                    if !reaching.is_empty() {
+                        let from = sub_mod(reaching.start, offset, length);
+                        let arc = reaching.len();
+                        if arc <= length - from {
+                            threshold(from, 1);
+                            threshold(from + arc, -1);
+                        } else {
+                            threshold(0, 1);
+                            threshold(arc - (length - from), -1);
+                            threshold(from, 1);
+                        }
+                    }
+                    tree.offsets.push(turned(offset, &cmd, length));
+                }
+            }
+            let before = tree.passes[i];
+            tree.passes.push(
+                before
+                    .checked_add(passes)
+                    .context("Pass count overflowed")?,
+            );
+        }
+
+        for i in (1..size).rev() {
+            tree.nodes[i] = [&tree.nodes[2 * i][..], &tree.nodes[2 * i + 1][..]].concat();
+        }
+        for node in &mut tree.nodes {
+            node.sort_unstable();
+            let mut sum = 0;
+            for (_, sign) in node.iter_mut() {
+                sum += *sign;
+                *sign = sum;
+            }
+        }
+        for m in 1..tree.sets.len() {
+            let (from, position) = tree.sets[m - 1];
+            let (_, passes) = tree.segment(position, from + 1..tree.sets[m].0)?;
+            let before = tree.set_passes[m - 1];
+            tree.set_passes.push(
+                before
+                    .checked_add(passes)
+                    .context("Pass count overflowed")?,
+            );
+        }
+
+        Ok(tree)
+    }
+
+    /// Number of commands in the tree
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// End position and passes of the target when applying the commands in `range`
+    /// to a dial starting at `start`
+    pub fn query(&self, start: usize, range: Range<usize>) -> Result<(usize, usize)> {
+        if start >= self.length {
+            bail!(
+                "Start {} should be less than the dial length {}",
+                start,
+                self.length
+            );
+        }
+        if range.start > range.end || range.end > self.len {
+            bail!("Command range {:?} is out of bounds", range);
+        }
+
+        // The `=N` commands in the range split it into segments starting at known positions
+        let first = self.sets.partition_point(|&(i, _)| i < range.start);
+        let last = self.sets.partition_point(|&(i, _)| i < range.end);
+        if first == last {
+            return self.segment(start, range);
+        }
+        let (_, before) = self.segment(start, range.start..self.sets[first].0)?;
+        let between = self.set_passes[last - 1] - self.set_passes[first];
+        let (from, position) = self.sets[last - 1];
+        let (end, after) = self.segment(position, from + 1..range.end)?;
+        let passes = before
+            .checked_add(between)
+            .and_then(|passes| passes.checked_add(after))
+            .context("Pass count overflowed")?;
+
+        Ok((end, passes))
+    }
+
+    /// Passes of the target when applying the commands in `range` to a dial starting at `start`
+    pub fn passes(&self, start: usize, range: Range<usize>) -> Result<usize> {
+        Ok(self.query(start, range)?.1)
+    }
+
+    /// End position and passes of a range without `=N` commands from `start`
+    fn segment(&self, start: usize, range: Range<usize>) -> Result<(usize, usize)> {
+        let length = self.length;
+        let (from, to) = (self.offsets[range.start], self.offsets[range.end]);
+        let z = sub_mod(sub_mod(start, self.target, length), from, length);
+
+        let mut passes = (self.passes[range.end] - self.passes[range.start]) as i128;
+        let (mut lo, mut hi) = (range.start + self.size, range.end + self.size);
+        while lo < hi {
+            if lo % 2 == 1 {
+                passes += self.signs_up_to(lo, z) as i128;
+                lo += 1;
+            }
+            if hi % 2 == 1 {
+                hi -= 1;
+                passes += self.signs_up_to(hi, z) as i128;
+            }
+            lo /= 2;
+            hi /= 2;
+        }
+
+        Ok((
+            add_mod(start, sub_mod(to, from, length), length),
+            passes.try_into().context("Pass count overflowed")?,
+        ))
+    }
+
+    /// Sum of the signs of the thresholds of a node up to `z`
+    fn signs_up_to(&self, node: usize, z: usize) -> isize {
+        let node = &self.nodes[node];
+        match node.partition_point(|&(threshold, _)| threshold <= z) {
+            0 => 0,
+            i => node[i - 1].1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dial_landings_right() {
+        let mut dial = Dial::new(100, 20, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: 20,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 40);
+        assert_eq!(dial.landings(), 0);
+    }
+
+    #[test]
+    fn test_dial_landings_left() {
+        let mut dial = Dial::new(100, 20, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Left,
+            amount: 10,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 10);
+        assert_eq!(dial.landings(), 0);
+    }
+
+    #[test]
+    fn test_dial_landings_right_wrap() {
+        let mut dial = Dial::new(100, 20, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: 80,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.landings(), 1);
+    }
+
+    #[test]
+    fn test_dial_landings_left_wrap() {
+        let mut dial = Dial::new(100, 5, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Left,
+            amount: 10,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 95);
+        assert_eq!(dial.landings(), 0);
+    }
+
+    #[test]
+    fn test_solve_part1_counts_correctly() {
+        let lines = vec![
+            "L68".to_string(),
+            "L30".to_string(),
+            "R48".to_string(),
+            "L5".to_string(),
+            "R60".to_string(),
+            "L55".to_string(),
+            "L1".to_string(),
+            "L99".to_string(),
+            "R14".to_string(),
+            "L82".to_string(),
+        ];
+        assert_eq!(solve_part1(&lines).unwrap(), "3");
+    }
+
+    #[test]
+    fn test_dial_passes_right() {
+        let mut dial = Dial::new(100, 20, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: 20,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 40);
+    }
+
+    #[test]
+    fn test_dial_passes_left() {
+        let mut dial = Dial::new(100, 20, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Left,
+            amount: 10,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 10);
+    }
+
+    #[test]
+    fn test_dial_passes_right_wrap() {
+        let mut dial = Dial::new(100, 75, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: 125,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.passes(), 2);
+    }
+
+    #[test]
+    fn test_dial_passes_left_wrap() {
+        let mut dial = Dial::new(100, 75, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Left,
+            amount: 125,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 50);
+        assert_eq!(dial.passes(), 1);
+    }
+
+    #[test]
+    fn test_dial_passes_right_ends_at_zero() {
+        let mut dial = Dial::new(100, 52, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: 48,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.passes(), 1);
+    }
+
+    #[test]
+    fn test_dial_passes_left_ends_at_zero() {
+        let mut dial = Dial::new(100, 55, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Left,
+            amount: 55,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.passes(), 1);
+    }
+
+    #[test]
+    fn test_dial_passes_right_starts_at_zero() {
+        let mut dial = Dial::new(100, 0, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: 14,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 14);
+        assert_eq!(dial.passes(), 0);
+    }
+
+    #[test]
+    fn test_dial_passes_left_starts_at_zero() {
+        let mut dial = Dial::new(100, 0, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Left,
+            amount: 5,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), 95);
+        assert_eq!(dial.passes(), 0);
+    }
+
+    #[test]
+    fn test_solve_part2_counts_correctly() {
+        let lines = vec![
+            "L68".to_string(),
+            "L30".to_string(),
+            "R48".to_string(),
+            "L5".to_string(),
+            "R60".to_string(),
+            "L55".to_string(),
+            "L1".to_string(),
+            "L99".to_string(),
+            "R14".to_string(),
+            "L82".to_string(),
+        ];
+        assert_eq!(solve_part2(&lines).unwrap(), "6");
+    }
+
+    #[test]
+    fn test_dial_landings_huge_amount_does_not_overflow() {
+        let mut dial = Dial::new(100, 50, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: usize::MAX,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(dial.position(), (50 + usize::MAX % 100) % 100);
+    }
+
+    #[test]
+    fn test_dial_passes_huge_amount_does_not_overflow() {
+        let mut dial = Dial::new(100, 50, 0).unwrap();
+        let cmd = RotationCmd {
+            direction: Direction::Right,
+            amount: usize::MAX,
+        };
+
+        dial.rotate(&cmd).unwrap();
+
+        assert_eq!(
+            dial.passes(),
+            usize::MAX / 100 + (50 + usize::MAX % 100) / 100
+        );
+    }
+
+    #[test]
+    fn test_dial_non_zero_target() {
+        let mut dial = Dial::new(10, 3, 7).unwrap();
+
+        dial.rotate(&RotationCmd {
+            direction: Direction::Right,
+            amount: 14,
+        })
+        .unwrap();
+        assert_eq!((dial.position(), dial.landings(), dial.passes()), (7, 1, 2));
+
+        dial.rotate(&RotationCmd {
+            direction: Direction::Left,
+            amount: 10,
+        })
+        .unwrap();
+        assert_eq!((dial.position(), dial.landings(), dial.passes()), (7, 2, 3));
+
+        dial.rotate(&RotationCmd {
+            direction: Direction::Left,
+            amount: 9,
+        })
+        .unwrap();
+        assert_eq!((dial.position(), dial.landings(), dial.passes()), (8, 2, 3));
+    }
+
+    #[test]
+    fn test_dial_matches_click_by_click_simulation() {
+        for (length, target) in [(1, 0), (7, 0), (7, 3), (10, 9)] {
+            for start in 0..length {
+                for amount in 0..3 * length {
+                    for direction in [Direction::Right, Direction::Left] {
+                        let step = match direction {
+                            Direction::Right => 1,
+                            Direction::Left => length - 1,
+                        };
+                        let mut position = start;
+                        let mut passes = 0;
+                        for _ in 0..amount {
+                            position = (position + step) % length;
+                            passes += usize::from(position == target);
+                        }
+
+                        let mut dial = Dial::new(length, start, target).unwrap();
+                        dial.rotate(&RotationCmd { direction, amount }).unwrap();
+
+                        assert_eq!((dial.position(), dial.passes()), (position, passes));
+                        assert_eq!(dial.landings(), usize::from(position == target));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dial_params() {
+        let lines = vec![
+            "L68".to_string(),
+            "L30".to_string(),
+            "R48".to_string(),
+            "R100".to_string(),
+        ];
+        let mut params = Params::default();
+        params.set(Day01::PARAMS, "dial_target", 52).unwrap();
+
+        assert_eq!(Day01::part1_with(&lines, &params).unwrap(), "1");
+        assert_eq!(Day01::part2_with(&lines, &params).unwrap(), "2");
+
+        params.set(Day01::PARAMS, "dial_length", 50).unwrap();
+        assert!(Day01::part1_with(&lines, &params).is_err());
+    }
+
+    #[test]
+    fn test_dial_huge_length_does_not_overflow() {
+        let mut dial = Dial::new(usize::MAX, 5, 0).unwrap();
+
+        dial.rotate(&RotationCmd {
+            direction: Direction::Left,
+            amount: 10,
+        })
+        .unwrap();
+        assert_eq!((dial.position(), dial.passes()), (usize::MAX - 5, 1));
+
+        dial.rotate(&RotationCmd {
+            direction: Direction::Right,
+            amount: 10,
+        })
+        .unwrap();
+        assert_eq!((dial.position(), dial.passes()), (5, 2));
+
+        let lines = vec!["L10".to_string(), "R5".to_string()];
+        let mut params = Params::default();
+        params
+            .set(Day01::PARAMS, "dial_length", usize::MAX)
+            .unwrap();
+        params.set(Day01::PARAMS, "dial_start", 5).unwrap();
+        assert_eq!(Day01::part1_with(&lines, &params).unwrap(), "1");
+        assert_eq!(Day01::part2_with(&lines, &params).unwrap(), "2");
+    }
+
+    #[test]
+    fn test_position_histogram_length_is_bounded() {
+        let lines = vec!["R1".to_string()];
+
+        assert!(position_histogram(&lines, MAX_HISTOGRAM_LENGTH, 0).is_ok());
+        let err = position_histogram(&lines, usize::MAX, 5).unwrap_err();
+        assert!(err.to_string().contains("limit of 1048576 positions"));
+        assert!(StartCounts::new(&lines, usize::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn test_position_histogram_example() {
+        let lines: Vec<String> = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+
+        let histogram = position_histogram(&lines, 100, 50).unwrap();
+
+        assert_eq!(
+            histogram.landings[0].to_string(),
+            solve_part1(&lines).unwrap()
+        );
+        assert_eq!(
+            histogram.passes[0].to_string(),
+            solve_part2(&lines).unwrap()
+        );
+        assert_eq!(histogram.most_landed(), (0, 3));
+        assert_eq!(histogram.landings.iter().sum::<usize>(), lines.len());
+    }
+
+    #[test]
+    fn test_position_histogram_matches_dial_for_every_target() {
+        let lines: Vec<String> = ["R23", "L4", "L40", "R9", "R0", "L17", "R70"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        for (length, start) in [(1, 0), (10, 3), (13, 12)] {
+            let histogram = position_histogram(&lines, length, start).unwrap();
+
+            for target in 0..length {
+                let dial = turn_dial(&lines, Dial::new(length, start, target).unwrap()).unwrap();
+                assert_eq!(histogram.landings[target], dial.landings());
+                assert_eq!(histogram.passes[target], dial.passes());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_tree_matches_dial() {
+        let scenarios = [
+            (
+                vec!["R23", "L4", "L40", "R9", "R0", "L17", "R70", "L250", "R5"],
+                2,
+            ),
+            (
+                vec![
+                    "R30", "=9", "L25", "=0", "R41", "L3", "=12", "=4", "R8", "L60",
+                ],
+                0,
+            ),
+        ];
+
+        for (lines, target) in scenarios {
+            let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+            let tree = RotationTree::new(&lines, 13, target).unwrap();
+
+            for start in 0..13 {
+                for i in 0..=lines.len() {
+                    for j in i..=lines.len() {
+                        let dial = Dial::new(13, start, target).unwrap();
+                        let dial = turn_dial(&lines[i..j], dial).unwrap();
+                        assert_eq!(
+                            tree.query(start, i..j).unwrap(),
+                            (dial.position(), dial.passes())
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_tree_example_and_bounds() {
+        let lines: Vec<String> = [
+            "L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        let tree = RotationTree::new(&lines, 100, 0).unwrap();
+
+        assert_eq!(tree.len(), 10);
+        assert_eq!(tree.passes(50, 0..10).unwrap(), 6);
+        assert_eq!(tree.passes(50, 4..4).unwrap(), 0);
+        assert!(tree.query(100, 0..1).is_err());
+        assert!(tree.query(0, 0..11).is_err());
+        assert!(RotationTree::new(&[], 100, 0).unwrap().is_empty());
+
+        let lines: Vec<String> = ["L10", "R5", "R20"].iter().map(|l| l.to_string()).collect();
+        let tree = RotationTree::new(&lines, usize::MAX, 0).unwrap();
+        assert_eq!(tree.query(5, 0..3).unwrap(), (20, 2));
+        assert_eq!(tree.query(5, 1..3).unwrap(), (30, 0));
+    }
+
+    #[test]
+    fn test_start_counts_match_dial() {
+        let lines: Vec<String> = ["R23", "L4", "L40", "R9", "R0", "L17", "R70"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let counts = StartCounts::new(&lines, 11, 4).unwrap();
+
+        for start in 0..11 {
+            let dial = turn_dial(&lines, Dial::new(11, start, 4).unwrap()).unwrap();
+            assert_eq!(counts.landings[start], dial.landings());
+            assert_eq!(counts.passes[start], dial.passes());
+        }
+    }
+
+    #[test]
+    fn test_start_counts_queries() {
+        let lines: Vec<String> = ["R3", "L1"].iter().map(|l| l.to_string()).collect();
+        let counts = StartCounts::new(&lines, 4, 0).unwrap();
+
+        // Starting at 0..4 ends the first rotation at 3, 0, 1, 2 and the second at 2, 3, 0, 1
+        assert_eq!(counts.landings, vec![0, 1, 1, 0]);
+        assert_eq!(counts.starts_with(1, 1).unwrap(), vec![1, 2]);
+        assert_eq!(counts.max(2).unwrap(), (2, 2));
+        assert_eq!(counts.min(2).unwrap(), (0, 0));
+        assert!(counts.starts_with(3, 0).is_err());
+    }
+
+    #[test]
+    fn test_histogram_and_start_counts_exports() {
+        let lines: Vec<String> = ["R3", "L1"].iter().map(|l| l.to_string()).collect();
+        let mut params = Params::default();
+        params.set(Day01::PARAMS, "dial_length", 4).unwrap();
+        params.set(Day01::PARAMS, "dial_start", 0).unwrap();
+        params.set(Day01::PARAMS, "dial_target", 0).unwrap();
+
+        assert_eq!(
+            Day01::export(&lines, "histogram", &params).unwrap(),
+            "position,landings,passes\n0,0,0\n1,0,1\n2,1,2\n3,1,1\n"
+        );
+        assert_eq!(
+            Day01::export(&lines, "start-counts", &params).unwrap(),
+            "start,landings,passes\n0,0,0\n1,1,1\n2,1,2\n3,0,1\n"
+        );
+    }
+
+    #[test]
+    fn test_solve_part1_empty_line_returns_error() {
+        let lines = vec!["R5".to_string(), "".to_string()];
+
+        assert!(solve_part1(&lines).is_err());
+    }
+
+    #[test]
+    fn test_solve_part1_too_large_amount_returns_error() {
+        let lines = vec!["R99999999999999999999999".to_string()];
+
+        assert!(solve_part1(&lines).is_err());
+    }
+
+    /// Expanded commands of a single line, displayed
+    fn expand(line: &str) -> Result<String> {
+        let program = parse_line(line)?;
+        Ok(commands(&program)
+            .map(|cmd| cmd.to_string())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    #[test]
+    fn test_parse_line_separators_groups_and_comments() {
+        assert_eq!(expand("R5").unwrap(), "R5");
+        assert_eq!(expand("R5, L3  R2,L1").unwrap(), "R5 L3 R2 L1");
+        assert_eq!(
+            expand("(R5 L3)x3 =7 # back to 7").unwrap(),
+            "R5 L3 R5 L3 R5 L3 =7"
+        );
+        assert_eq!(expand("((R1)x2, L1)x2").unwrap(), "R1 R1 L1 R1 R1 L1");
+        assert_eq!(expand("# only a comment").unwrap(), "");
+        assert_eq!(expand("(R1)x0 ()x5 R2").unwrap(), "R2");
+        assert_eq!(expand("(2R15 3=4)x2").unwrap(), "2R15 3=4 2R15 3=4");
+    }
+
+    #[test]
+    fn test_repeats_expand_lazily() {
+        let program = parse_line("(R1 (L2)x1000000000000)x1000000000000 ()x99999999999").unwrap();
+        let first: Vec<String> = commands(&program).take(3).map(|c| c.to_string()).collect();
+
+        assert_eq!(first, vec!["R1", "L2", "L2"]);
+        assert_eq!(
+            commands(&parse_line("(()x9999999999)x9999999999").unwrap()).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_parse_line_errors() {
+        let error = |line: &str| format!("{:#}", parse_line(line).unwrap_err());
+
+        assert!(error("(R5 L3").contains("Unclosed '(' at column 1"));
+        assert!(error("R5)").contains("Unmatched ')' at column 3"));
+        assert!(error("(R5)").contains("repeat count like 'x3' at column 5"));
+        assert!(error("(R5)x").contains("number at column 6"));
+        assert!(error("R5 Q1").contains("Unknown command 'Q' at column 4"));
+        assert!(error("=").contains("number at column 2"));
+        assert!(error(&"(".repeat(40)).contains("nested at most 32 deep"));
+        assert!(error("   ").contains("Should have a command or a comment"));
+        assert!(error("0R1").contains("numbered from 1, got 0 at column 1"));
+        assert!(error("R1 2").contains("Should have a command at column 5"));
+    }
+
+    #[test]
+    fn test_set_moves_without_counting() {
+        let lines: Vec<String> = ["R50", "=50", "L50, =99 R1"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        assert_eq!(solve_part1(&lines).unwrap(), "3");
+        assert_eq!(solve_part2(&lines).unwrap(), "3");
+        assert!(solve_part1(&["=100".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_lock_turns_wheels_together_or_by_number() {
+        let wheels = vec![
+            Dial::new(10, 0, 5).unwrap(),
+            Dial::new(7, 0, 5).unwrap(),
+            Dial::new(4, 0, 1).unwrap(),
+        ];
+        let mut lock = Lock::new(wheels, false).unwrap();
+
+        lock.run(&["R5, 3L3 # every wheel on its target, then not".to_string()])
+            .unwrap();
+        lock.run(&["2R7, R10 =0 (1L1)x2".to_string()]).unwrap();
+
+        let positions: Vec<usize> = lock.wheels().iter().map(Dial::position).collect();
+        let landings: Vec<usize> = lock.wheels().iter().map(Dial::landings).collect();
+        let passes: Vec<usize> = lock.wheels().iter().map(Dial::passes).collect();
+        assert_eq!(positions, vec![8, 0, 0]);
+        assert_eq!(landings, vec![2, 2, 1]);
+        assert_eq!(passes, vec![2, 3, 4]);
+        assert_eq!(lock.first_alignments(), &[1]);
+        assert!(lock.run(&["4R1".to_string()]).is_err());
+        assert!(turn_dial(&["2R1".to_string()], Dial::new(10, 0, 0).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_lock_carries_like_an_odometer() {
+        let wheels = vec![Dial::new(10, 0, 0).unwrap(); 3];
+        let mut lock = Lock::new(wheels, true).unwrap();
+
+        lock.run(&["1R123".to_string()]).unwrap();
+        let positions: Vec<usize> = lock.wheels().iter().map(Dial::position).collect();
+        assert_eq!(positions, vec![3, 2, 1]);
+
+        lock.run(&["1L123".to_string()]).unwrap();
+        let positions: Vec<usize> = lock.wheels().iter().map(Dial::position).collect();
+        assert_eq!(positions, vec![0, 0, 0]);
+        assert_eq!(lock.first_alignments(), &[2]);
+    }
+
+    #[test]
+    fn test_lock_counts_alignments_past_those_listed() {
+        let mut lock = Lock::new(vec![Dial::new(10, 0, 0).unwrap(); 2], false).unwrap();
+
+        lock.run(&["(R10)x5000".to_string()]).unwrap();
+
+        assert_eq!(lock.alignments(), 5000);
+        assert_eq!(lock.first_alignments().len(), MAX_LISTED_ALIGNMENTS);
+        assert_eq!(lock.first_alignments()[999], 1000);
+    }
+
+    #[test]
+    fn test_inspect_shows_dial_after_each_command() {
+        let lines = vec!["L68, =0".to_string(), "(R50)x3".to_string()];
+
+        assert_eq!(
+            Day01::inspect(&lines, &Params::default(), 4).unwrap(),
+            vec![
+                "L68: dial at 82, 0 landings, 1 passes",
+                "=0: dial at 0, 0 landings, 1 passes",
+                "R50: dial at 50, 0 landings, 1 passes",
+                "R50: dial at 0, 1 landings, 2 passes",
+            ]
+        );
+        assert!(Day01::inspect(&vec!["2R1".to_string()], &Params::default(), 4).is_err());
+    }
+
+    #[test]
+    fn test_lock_exports() {
+        let lines = vec!["R5, 3L3".to_string(), "2R7 R10".to_string()];
+        let mut params = Params::default();
+        params.set(Day01::PARAMS, "dial_length", 10).unwrap();
+        params.set(Day01::PARAMS, "dial_start", 0).unwrap();
+        params.set(Day01::PARAMS, "dial_target", 5).unwrap();
+        params.set(Day01::PARAMS, "wheels", 3).unwrap();
+        params.set(Day01::PARAMS, "dial_length.2", 12).unwrap();
+        params.set(Day01::PARAMS, "dial_length.3", 14).unwrap();
+
+        assert_eq!(
+            Day01::export(&lines, "lock", &params).unwrap(),
+            "wheel,length,target,end,landings,passes,lock_alignments\n\
+             1,10,5,5,2,2,1\n\
+             2,12,5,10,1,2,1\n\
+             3,14,5,12,1,2,1\n"
+        );
+        assert_eq!(
+            Day01::export(&lines, "lock-alignments", &params).unwrap(),
+            "command\n1\n"
+        );
+
+        // Wheel 3 starting at 1 and targeting 4 is never on its target with the others, and
+        // with carry wheel 2 wrapping past 0 turns it onto 4
+        params.set(Day01::PARAMS, "dial_start.3", 1).unwrap();
+        params.set(Day01::PARAMS, "dial_target.3", 4).unwrap();
+        params.set(Day01::PARAMS, "carry", 1).unwrap();
+        assert_eq!(
+            Day01::export(&lines, "lock", &params).unwrap(),
+            "wheel,length,target,end,landings,passes,lock_alignments\n\
+             1,10,5,5,2,2,0\n\
+             2,12,5,11,1,2,0\n\
+             3,14,4,0,1,3,0\n"
+        );
+
+        assert!(params.set(Day01::PARAMS, "carry", 2).is_err());
+        params.set(Day01::PARAMS, "dial_start.2", 12).unwrap();
+        let error = format!("{:#}", Day01::export(&lines, "lock", &params).unwrap_err());
+        assert!(error.starts_with("Wheel 2 of the lock: Dial start 12"));
+        params.set(Day01::PARAMS, "wheels", 0).unwrap();
+        assert!(Day01::export(&lines, "lock", &params).is_err());
+    }
+
+    #[test]
+    fn test_one_wheel_lock_export_matches_answers() {
+        let lines: Vec<String> = ["L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        let csv = Day01::export(&lines, "lock", &Params::default()).unwrap();
+
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            format!(
+                "1,100,0,{},{},{},{}",
+                turn_dial(&lines, Dial::new(100, 50, 0).unwrap())
+                    .unwrap()
+                    .position(),
+                solve_part1(&lines).unwrap(),
+                solve_part2(&lines).unwrap(),
+                // A single wheel is aligned whenever it lands on its target
+                solve_part1(&lines).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_zero_events_csv() {
+        let lines: Vec<String> = ["L68", "L30", "R48", "R5, (L3)x2 # wraps", "R200"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        let csv = zero_events_csv(&lines, Dial::new(100, 50, 0).unwrap()).unwrap();
+
+        assert_eq!(
+            csv,
+            "line,commands,start,end,passes,landings\n\
+             1,L68,50,82,1,0\n\
+             2,L30,82,52,0,0\n\
+             3,R48,52,0,1,1\n\
+             4,\"R5, (L3)x2 # wraps\",0,99,1,0\n\
+             5,R200,99,99,2,0\n"
+        );
+    }
+
+    #[test]
+    fn test_zero_events_csv_sums_to_answers() {
+        let lines: Vec<String> = [
+            "L68",
+            "L30",
+            "R48",
+            "L5",
+            "R60",
+            "L55",
+            "L1",
+            "L99",
+            "R14",
+            "L82",
+            "R68 (L100)x3, R1 L1",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+
+        let csv = Day01::export(&lines, "zero-events", &Params::default()).unwrap();
+        let rows: Vec<Vec<&str>> = csv
+            .lines()
+            .skip(1)
+            // The commands field may be quoted and hold commas, the counts are last
+            .map(|r| r.rsplit(',').collect())
+            .collect();
+
+        let column = |i: usize| -> usize {
+            rows.iter()
+                .map(|row| row[i].parse::<usize>().unwrap())
+                .sum()
+        };
+        let (landings, passes) = (column(0), column(1));
+        assert!(landings > rows.iter().filter(|row| row[0] != "0").count());
+        assert_eq!(landings.to_string(), solve_part1(&lines).unwrap());
+        assert_eq!(passes.to_string(), solve_part2(&lines).unwrap());
+    }
+
+    #[test]
+    fn test_scenarios_reach_every_entry_point() {
+        let lines: Vec<String> = ["R5, L3", "# comment", "(R60 L10)x3", "L200 R17"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let length = 100;
+
+        let histogram = position_histogram(&lines, length, 50).unwrap();
+        let counts = StartCounts::new(&lines, length, 0).unwrap();
+        let tree = RotationTree::new(&lines, length, 0).unwrap();
+        assert_eq!(tree.len(), 10);
+
+        for target in 0..length {
+            let dial = turn_dial(&lines, Dial::new(length, 50, target).unwrap()).unwrap();
+            assert_eq!(histogram.landings[target], dial.landings());
+            assert_eq!(histogram.passes[target], dial.passes());
+        }
+        for start in 0..length {
+            let dial = turn_dial(&lines, Dial::new(length, start, 0).unwrap()).unwrap();
+            assert_eq!(counts.landings[start], dial.landings());
+            assert_eq!(counts.passes[start], dial.passes());
+            assert_eq!(
+                tree.query(start, 0..10).unwrap(),
+                (dial.position(), dial.passes())
+            );
+        }
+        assert_eq!(
+            solve_part1(&lines).unwrap(),
+            histogram.landings[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_set_commands_in_every_entry_point() {
+        let lines: Vec<String> = ["R30 =90", "(L25 =10)x2", "R95"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        let histogram = position_histogram(&lines, 100, 50).unwrap();
+        let tree = RotationTree::new(&lines, 100, 0).unwrap();
+        for start in [0, 50, 99] {
+            let dial = turn_dial(&lines, Dial::new(100, start, 0).unwrap()).unwrap();
+            assert_eq!(
+                tree.query(start, 0..tree.len()).unwrap(),
+                (dial.position(), dial.passes())
+            );
+        }
+        let dial = turn_dial(&lines, Dial::new(100, 50, 0).unwrap()).unwrap();
+        assert_eq!(histogram.passes[0], dial.passes());
+
+        let error = StartCounts::new(&lines, 100, 0).unwrap_err();
+        assert!(format!("{}", error).contains("`=N`"));
+        assert!(position_histogram(&["=100".to_string()], 100, 0).is_err());
+        assert!(RotationTree::new(&["2R1".to_string()], 100, 0).is_err());
+        assert!(RotationTree::new(&["(R1)x100000000".to_string()], 100, 0).is_err());
+    }
+
+    #[test]
+    fn test_huge_repeats_are_solved_without_expanding() {
+        let huge = ["(R1)x18446744073709551615".to_string()];
+        let passes = ((usize::MAX as u128 + 50) / 100).to_string();
+
+        assert_eq!(solve_part1(&huge).unwrap(), passes);
+        assert_eq!(solve_part2(&huge).unwrap(), passes);
+        let nested = ["((R1)x10000)x10001".to_string()];
+        assert_eq!(solve_part2(&nested).unwrap(), "1000100");
+        let csv = zero_events_csv(&huge, Dial::new(100, 50, 0).unwrap()).unwrap();
+        assert!(csv.ends_with(&format!(",50,65,{},{}\n", passes, passes)));
+        assert!(solve_part2(&["(R100)x18446744073709551615 R100".to_string()]).is_err());
+    }
+
+    /// Dial after applying the commands of `lines` one at a time
+    fn expanded_dial(lines: &[String], mut dial: Dial) -> Result<Dial> {
+        for step in commands(&parse_program(lines)?) {
+            match dial_command(&step)? {
+                Command::Rotate(cmd) => dial.rotate(&cmd)?,
+                Command::Set(position) => dial.set(position)?,
+            }
+        }
+        Ok(dial)
+    }
+
+    #[test]
+    fn test_repeats_match_expanded_commands() {
+        let scenarios = [
+            "(R3)x7",
+            "(R5 L3)x40",
+            "(L7 R2 L13)x25 R1",
+            "((R4 L1)x3 L9)x11",
+            "(R2 (L5)x3 (R1 (L2)x2)x4)x9",
+            "(R6 =3 L4)x17",
+            "((L3)x2 =0 R11)x5 (R1)x0",
+            "(R0 L0)x1000",
+        ];
+
+        for scenario in scenarios {
+            let lines = vec![scenario.to_string()];
+            for (length, target) in [(1, 0), (7, 3), (10, 0), (12, 11)] {
+                for start in 0..length {
+                    let dial = Dial::new(length, start, target).unwrap();
+                    assert_eq!(
+                        turn_dial(&lines, dial).ok(),
+                        expanded_dial(&lines, dial).ok(),
+                        "{} on a dial of {} from {}",
+                        scenario,
+                        length,
+                        start
+                    );
+                }
+            }
+        }
+        assert!(turn_dial(&["((2R1)x3)x2".to_string()], Dial::new(10, 0, 0).unwrap()).is_err());
+        assert!(
+            turn_dial(
+                &["(R1 (=10)x2)x2".to_string()],
+                Dial::new(10, 0, 0).unwrap()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_count_at_least() {
+        for (length, offset, shift) in [(1, 0, 0), (7, 3, 5), (10, 9, 0), (12, 4, 8)] {
+            for threshold in 0..=length {
+                let expected = (0..50)
+                    .filter(|j| (offset + j * shift) % length >= threshold)
+                    .count();
+                assert_eq!(
+                    count_at_least(50, length, offset, shift, threshold),
+                    expected as u128
+                );
+            }
+        }
+        assert_eq!(
+            count_at_least(usize::MAX, usize::MAX, 5, usize::MAX - 1, 0),
+            usize::MAX as u128
+        );
+    }
+
+    #[test]
+    fn test_check_reports_bad_lines() {
+        let input = "L68\nX30\n\nR\nR48\n";
+
+        let problems = Day01::check(input);
+        assert_eq!(
+            problems.iter().map(|p| p.line).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+    }
+}
@@ -0,0 +1,822 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::check::{Problem, numbered_lines};
+use crate::solution::{Param, ParamKind, Params, Solution};
+use crate::trace;
+use crate::trace::Level;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = String;
+
+    const VERSION: u32 = 2;
+
+    const PARAMS: &'static [Param] = &[BASE];
+
+    const EXPORTS: &'static [&'static str] = &["ids", "repeats"];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<String> {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<String> {
+        solve_part2(input)
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Result<String> {
+        solve_part1_in_base(input, params.get(&BASE) as u64)
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Result<String> {
+        solve_part2_in_base(input, params.get(&BASE) as u64)
+    }
+
+    fn export(input: &Self::Input, name: &str, params: &Params) -> Result<String> {
+        let base = params.get(&BASE) as u64;
+        match name {
+            "ids" => ids_csv(input, base),
+            "repeats" => Ok(repeats_csv(&analyse(input, base)?)),
+            _ => bail!("Unknown export '{}'", name),
+        }
+    }
+
+    fn check(input: &str) -> Vec<Problem> {
+        Self::check_with(input, &Params::default())
+    }
+
+    fn check_with(input: &str, params: &Params) -> Vec<Problem> {
+        let base = params.get(&BASE) as u64;
+        if let Err(err) = check_base(base) {
+            return vec![Problem::new(0, format!("{:#}", err))];
+        }
+        let mut problems = vec![];
+        let mut num_lines: usize = 0;
+
+        for (line_no, line) in numbered_lines(input) {
+            num_lines += 1;
+            if num_lines == 2 {
+                problems.push(Problem::new(line_no, "ranges should be on a single line"));
+            }
+
+            for range in line.split(',') {
+                if let Err(err) = parse_range(range, base) {
+                    problems.push(Problem::new(line_no, format!("{:#}", err)));
+                }
+            }
+        }
+
+        if num_lines == 0 {
+            problems.push(Problem::new(0, "input is empty"));
+        }
+
+        problems
+    }
+}
+
+const BASE: Param = Param {
+    name: "base",
+    default: 10,
+    description: "Base of the range endpoints and of the digits that repeat, 2 to 36",
+    kind: ParamKind::Number,
+};
+
+pub fn solve_part1(input: &str) -> Result<String> {
+    solve_part1_in_base(input, 10)
+}
+
+pub fn solve_part2(input: &str) -> Result<String> {
+    solve_part2_in_base(input, 10)
+}
+
+/// Part 1 with the range endpoints and IDs written in `base`, the sum still in decimal
+pub fn solve_part1_in_base(input: &str, base: u64) -> Result<String> {
+    Ok(analyse(input, base)?.part1()?.to_string())
+}
+
+/// Part 2 with the range endpoints and IDs written in `base`, the sum still in decimal
+pub fn solve_part2_in_base(input: &str, base: u64) -> Result<String> {
+    Ok(analyse(input, base)?.part2()?.to_string())
+}
+
+/// Totals of the IDs made of a repeated block of digits over every range, which both parts
+/// are queries of
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Analysis {
+    /// Base the IDs are written in
+    pub base: u64,
+    /// Totals of the IDs by number of repeats of their shortest block
+    pub by_repeats: BTreeMap<u32, RepeatTotals>,
+}
+
+/// Number and sum of the IDs whose shortest block repeats a given number of times
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepeatTotals {
+    pub count: u64,
+    pub sum: u128,
+}
+
+impl Analysis {
+    /// Sum of the IDs whose shortest block repeats a number of times accepted by `repeats`
+    pub fn sum_where(&self, repeats: impl Fn(u32) -> bool) -> Result<u128> {
+        self.by_repeats
+            .iter()
+            .filter(|&(&count, _)| repeats(count))
+            .try_fold(0u128, |sum, (_, totals)| {
+                sum.checked_add(totals.sum)
+                    .ok_or_else(|| anyhow!("Sum overflowed when adding {}", totals.sum))
+            })
+    }
+
+    /// Sum of the IDs made of a block repeated exactly twice, which are those whose shortest
+    /// block repeats an even number of times
+    pub fn part1(&self) -> Result<u128> {
+        self.sum_where(|repeats| repeats % 2 == 0)
+    }
+
+    /// Sum of every ID made of a repeated block
+    pub fn part2(&self) -> Result<u128> {
+        self.sum_where(|_| true)
+    }
+}
+
+/// Total the IDs made of a repeated block in every range, the endpoints written in `base`
+pub fn analyse(input: &str, base: u64) -> Result<Analysis> {
+    check_base(base)?;
+    let mut analysis = Analysis {
+        base,
+        ..Analysis::default()
+    };
+
+    for range in input.split(',') {
+        let (start, end) = parse_range(range, base)?;
+        for (repeats, range_totals) in range_totals(start, end, base) {
+            trace!(
+                Level::Debug,
+                "range {}-{}: {} IDs repeat their block {} times, summing to {}",
+                to_digits(start, base),
+                to_digits(end, base),
+                range_totals.count,
+                repeats,
+                range_totals.sum
+            );
+            let totals = analysis.by_repeats.entry(repeats).or_default();
+            totals.count += range_totals.count;
+            totals.sum = totals
+                .sum
+                .checked_add(range_totals.sum)
+                .with_context(|| format!("Sum overflowed when adding range '{}'", range.trim()))?;
+        }
+    }
+
+    Ok(analysis)
+}
+
+/// CSV of every ID with a repeated block, its shortest block and how often the block repeats,
+/// the IDs of each range in increasing order
+fn ids_csv(input: &str, base: u64) -> Result<String> {
+    check_base(base)?;
+    let mut csv = String::from("id,block,repeats\n");
+
+    for range in input.split(',') {
+        let (start, end) = parse_range(range, base)?;
+        let mut ids: Vec<RepeatedId> = repeated_ids(start, end, base)?.collect();
+        ids.sort_by_key(|repeated| repeated.id);
+        for repeated in ids {
+            csv += &format!(
+                "{},{},{}\n",
+                to_digits(repeated.id, base),
+                to_digits(repeated.block, base),
+                repeated.repeats
+            );
+        }
+    }
+
+    Ok(csv)
+}
+
+/// CSV of the number and sum of the IDs for each repeat count of their shortest block
+fn repeats_csv(analysis: &Analysis) -> String {
+    let mut csv = String::from("repeats,count,sum\n");
+    for (repeats, totals) in &analysis.by_repeats {
+        csv += &format!("{},{},{}\n", repeats, totals.count, totals.sum);
+    }
+
+    csv
+}
+
+fn check_base(base: u64) -> Result<()> {
+    if !(2..=36).contains(&base) {
+        bail!("Base should be 2 to 36, got {}", base);
+    }
+
+    Ok(())
+}
+
+/// Digits of bases up to 36 in order
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Digits of `x` in `base`, lowercase letters standing for digits above 9.
+/// The base has to be 2 to 36, as `check_base` ensures.
+fn to_digits(x: u64, base: u64) -> String {
+    let mut digits = vec![];
+    let mut rest = x;
+    loop {
+        digits.push(DIGITS[(rest % base) as usize] as char);
+        rest /= base;
+        if rest == 0 {
+            break;
+        }
+    }
+
+    digits.iter().rev().collect()
+}
+
+/// An ID made of a block of digits repeated at least twice, the block being as short as possible.
+/// Digits are those of the base the IDs were generated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepeatedId {
+    pub id: u64,
+    pub block: u64,
+    /// Number of digits of the block
+    pub block_len: u32,
+    pub repeats: u32,
+}
+
+/// Every ID in `start..=end` made of a repeated block of digits in `base`, each exactly once.
+/// IDs are built from their blocks for each number of digits and block length instead of
+/// testing every number of the range, so the work depends on the number of matching IDs.
+pub fn repeated_ids(start: u64, end: u64, base: u64) -> Result<impl Iterator<Item = RepeatedId>> {
+    check_base(base)?;
+
+    Ok(
+        block_lengths(end, base).flat_map(move |(block_len, repeats)| {
+            let (multiplier, blocks) = blocks(start, end, base as u128, block_len, repeats);
+
+            blocks
+                // A block that repeats itself generates its ID again with a shorter block
+                .filter(move |&block| !has_repeated_sequence(block as u64, base))
+                .map(move |block| RepeatedId {
+                    id: (block * multiplier) as u64,
+                    block: block as u64,
+                    block_len,
+                    repeats,
+                })
+        }),
+    )
+}
+
+/// Number and sum of the IDs in `start..=end` for each number of repeats of their shortest
+/// block, without generating the IDs. The IDs made of a given block length repeated form an
+/// arithmetic series, and those whose shortest block is shorter still are taken out again by
+/// Möbius inversion over the divisors of the block length, so the work only depends on the
+/// number of digits.
+fn range_totals(start: u64, end: u64, base: u64) -> BTreeMap<u32, RepeatTotals> {
+    let mut totals: BTreeMap<u32, RepeatTotals> = BTreeMap::new();
+
+    for (block_len, repeats) in block_lengths(end, base) {
+        let len = block_len * repeats;
+        // Sums of up to 20 digit IDs over up to 10 digit blocks fit an i128 many times over
+        let (count, sum) = divisors(block_len).fold((0i128, 0i128), |(count, sum), shorter| {
+            let mobius = mobius(block_len / shorter);
+            let (multiplier, blocks) = blocks(start, end, base as u128, shorter, len / shorter);
+            let (lowest, highest) = (*blocks.start(), *blocks.end());
+            if blocks.is_empty() || mobius == 0 {
+                return (count, sum);
+            }
+            let series_count = (highest - lowest + 1) as i128;
+            let series_sum =
+                (multiplier * ((lowest + highest) * (highest - lowest + 1) / 2)) as i128;
+
+            (count + mobius * series_count, sum + mobius * series_sum)
+        });
+        if count > 0 {
+            let entry = totals.entry(repeats).or_default();
+            entry.count += count as u64;
+            entry.sum += sum as u128;
+        }
+    }
+
+    totals
+}
+
+/// Every block length and number of repeats making up IDs of at most as many digits as `end`
+fn block_lengths(end: u64, base: u64) -> impl Iterator<Item = (u32, u32)> {
+    let max_len = end.checked_ilog(base).unwrap_or(0) + 1;
+
+    (2..=max_len).flat_map(|len| {
+        divisors(len)
+            .filter(move |&block_len| block_len < len)
+            .map(move |block_len| (block_len, len / block_len))
+    })
+}
+
+/// The multiplier turning a block of `block_len` digits into the ID of the block repeated
+/// `repeats` times, and the blocks whose ID falls in `start..=end`
+fn blocks(
+    start: u64,
+    end: u64,
+    base: u128,
+    block_len: u32,
+    repeats: u32,
+) -> (u128, RangeInclusive<u128>) {
+    let multiplier = multiplier(block_len, repeats, base);
+    let lowest = (start as u128)
+        .div_ceil(multiplier)
+        .max(base.pow(block_len - 1));
+    let highest = (end as u128 / multiplier).min(base.pow(block_len) - 1);
+
+    (multiplier, lowest..=highest)
+}
+
+/// 1, 0...01, 0...010...01 and so on with `repeats` ones `block_len` digits apart, computed in
+/// u128 because those of 20 digit IDs overflow u64
+fn multiplier(block_len: u32, repeats: u32, base: u128) -> u128 {
+    (0..repeats).map(|i| base.pow(block_len * i)).sum()
+}
+
+fn divisors(n: u32) -> impl Iterator<Item = u32> {
+    (1..=n).filter(move |&d| n.is_multiple_of(d))
+}
+
+/// Möbius function: 0 if `n` has a square factor, otherwise -1 or 1 for an odd or even
+/// number of prime factors
+fn mobius(n: u32) -> i128 {
+    let (mut rest, mut result) = (n, 1);
+    let mut p = 2;
+    while p * p <= rest {
+        if rest % p == 0 {
+            rest /= p;
+            if rest % p == 0 {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if rest > 1 {
+        result = -result;
+    }
+
+    result
+}
+
+/// Parse a range whose endpoints are written in `base`, the start not greater than the end
+fn parse_range(range: &str, base: u64) -> Result<(u64, u64)> {
+    let range = range.trim();
+    let (start_str, end_str) = range
+        .split_once('-')
+        .ok_or_else(|| anyhow!("Range should be in the form START-END: '{}'", range))?;
+    // `from_str_radix` would accept a leading sign
+    if start_str.starts_with('+') || end_str.starts_with('+') {
+        bail!("Range endpoints should be plain digits: '{}'", range);
+    }
+    let start = u64::from_str_radix(start_str, base as u32)
+        .with_context(|| format!("Should be able to parse range start in: '{}'", range))?;
+    let end = u64::from_str_radix(end_str, base as u32)
+        .with_context(|| format!("Should be able to parse range end in: '{}'", range))?;
+    if start > end {
+        bail!(
+            "Range start should not be greater than its end: '{}'",
+            range
+        );
+    }
+
+    Ok((start, end))
+}
+
+/// Number of digits of `x` in `base`, `None` for bases without digits
+fn num_digits(x: u64, base: u64) -> Option<u32> {
+    (base >= 2).then(|| x.checked_ilog(base).unwrap_or(0) + 1)
+}
+
+/// Whether the digits of `x` in `base` are some block of digits repeated exactly twice,
+/// the IDs part 1 looks for
+pub fn is_repeated_twice(x: u64, base: u64) -> bool {
+    let Some(len) = num_digits(x, base) else {
+        return false;
+    };
+
+    len % 2 == 0 && (x as u128).is_multiple_of(multiplier(len / 2, 2, base as u128))
+}
+
+/// Whether the digits of `x` in `base` are a block of digits repeated at least twice,
+/// which is when `x` is a multiple of the multiplier of a shorter block
+pub fn has_repeated_sequence(x: u64, base: u64) -> bool {
+    let Some(len) = num_digits(x, base) else {
+        return false;
+    };
+
+    divisors(len)
+        .filter(|&block_len| block_len < len)
+        .any(|block_len| {
+            (x as u128).is_multiple_of(multiplier(block_len, len / block_len, base as u128))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_repeated_twice_finds_seq_1() {
+        let input = 55;
+
+        assert!(is_repeated_twice(input, 10));
+    }
+
+    #[test]
+    fn test_is_repeated_twice_finds_seq_2() {
+        let input = 6464;
+
+        assert!(is_repeated_twice(input, 10));
+    }
+
+    #[test]
+    fn test_is_repeated_twice_finds_seq_3() {
+        let input = 123123;
+
+        assert!(is_repeated_twice(input, 10));
+    }
+
+    #[test]
+    fn test_is_repeated_twice_no_repetition_returns_false() {
+        let input = 12341235;
+
+        assert!(!is_repeated_twice(input, 10));
+    }
+
+    #[test]
+    fn test_has_repeated_sequence_finds_seq_1() {
+        let input = 12341234;
+
+        assert!(has_repeated_sequence(input, 10));
+    }
+
+    #[test]
+    fn test_has_repeated_sequence_finds_seq_2() {
+        let input = 123123123;
+
+        assert!(has_repeated_sequence(input, 10));
+    }
+
+    #[test]
+    fn test_has_repeated_sequence_finds_seq_3() {
+        let input = 1212121212;
+
+        assert!(has_repeated_sequence(input, 10));
+    }
+
+    #[test]
+    fn test_has_repeated_sequence_finds_seq_4() {
+        let input = 1111111;
+
+        assert!(has_repeated_sequence(input, 10));
+    }
+
+    #[test]
+    fn test_has_repeated_sequence_no_repetition_returns_false() {
+        let input = 12341235;
+
+        assert!(!has_repeated_sequence(input, 10));
+    }
+
+    #[test]
+    fn test_repetition_in_other_bases() {
+        // 0xabab, and 0b101101 which is 45
+        assert!(is_repeated_twice(0xabab, 16));
+        assert!(is_repeated_twice(45, 2));
+        assert!(!is_repeated_twice(0xabab, 10));
+        // 0x777 and 0b101010, which is 42
+        assert!(has_repeated_sequence(0x777, 16));
+        assert!(has_repeated_sequence(42, 2));
+        assert!(!has_repeated_sequence(42, 10));
+        assert!(!is_repeated_twice(11, 1));
+        assert!(!has_repeated_sequence(11, 0));
+    }
+
+    #[test]
+    fn test_solve_part1_counts_correctly() {
+        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+        assert_eq!(solve_part1(input).unwrap(), "1227775554");
+    }
+
+    #[test]
+    fn test_solve_part2_counts_correctly() {
+        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+        assert_eq!(solve_part2(input).unwrap(), "4174379265");
+    }
+
+    #[test]
+    fn test_has_repeated_sequence_single_digit_returns_false() {
+        let input = 0;
+
+        assert!(!has_repeated_sequence(input, 10));
+    }
+
+    #[test]
+    fn test_solve_part1_missing_dash_returns_error() {
+        let input = "11-22,9515";
+
+        assert!(solve_part1(input).is_err());
+    }
+
+    #[test]
+    fn test_solve_rejects_reversed_ranges_and_signs() {
+        assert!(solve_part1("22-11").is_err());
+        assert!(solve_part2("11-22,115-95").is_err());
+        assert!(solve_part1("+11-22").is_err());
+        assert!(solve_part2("11-+22").is_err());
+        assert_eq!(solve_part1("22-22").unwrap(), "22");
+    }
+
+    #[test]
+    fn test_solve_part2_empty_input_returns_error() {
+        let input = "";
+
+        assert!(solve_part2(input).is_err());
+    }
+
+    #[test]
+    fn test_solve_part1_sums_beyond_u64() {
+        let input =
+            "18446744071844674407-18446744071844674407,18446744071844674407-18446744071844674407";
+
+        assert_eq!(
+            solve_part1(input).unwrap(),
+            (2 * 18446744071844674407u128).to_string()
+        );
+    }
+
+    #[test]
+    fn test_solve_part1_very_wide_range() {
+        // Every block of k digits repeated twice, up to 14 digits
+        let expected: u128 = (1..=7)
+            .map(|k| {
+                let (lowest, highest) = (10u128.pow(k - 1), 10u128.pow(k) - 1);
+                (10u128.pow(k) + 1) * (lowest + highest) * (highest - lowest + 1) / 2
+            })
+            .sum();
+
+        assert_eq!(
+            solve_part1("1-99999999999999").unwrap(),
+            expected.to_string()
+        );
+        assert!(solve_part2("1-18446744073709551615").is_ok());
+    }
+
+    #[test]
+    fn test_range_totals_match_generated_ids() {
+        for base in [2, 3, 10, 16] {
+            for (start, end) in [(0, 5000), (17, 17), (100, 4321), (4096, 70000)] {
+                let mut expected: BTreeMap<u32, RepeatTotals> = BTreeMap::new();
+                for repeated in repeated_ids(start, end, base).unwrap() {
+                    let totals = expected.entry(repeated.repeats).or_default();
+                    totals.count += 1;
+                    totals.sum += repeated.id as u128;
+                }
+
+                assert_eq!(
+                    range_totals(start, end, base),
+                    expected,
+                    "base {} range {}-{}",
+                    base,
+                    start,
+                    end
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mobius() {
+        let values: Vec<i128> = (1..=12).map(mobius).collect();
+
+        assert_eq!(values, vec![1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0]);
+    }
+
+    #[test]
+    fn test_repeated_ids_matches_brute_force() {
+        let (start, end) = (1, 120_000);
+        let mut generated: Vec<u64> = repeated_ids(start, end, 10)
+            .unwrap()
+            .map(|r| r.id)
+            .collect();
+        generated.sort();
+
+        let expected: Vec<u64> = (start..=end)
+            .filter(|&x| has_repeated_sequence(x, 10))
+            .collect();
+        assert_eq!(generated, expected);
+    }
+
+    #[test]
+    fn test_repeated_ids_uses_shortest_block() {
+        let ids: Vec<RepeatedId> = repeated_ids(1111, 1111, 10).unwrap().collect();
+
+        assert_eq!(
+            ids,
+            vec![RepeatedId {
+                id: 1111,
+                block: 1,
+                block_len: 1,
+                repeats: 4
+            }]
+        );
+    }
+
+    #[test]
+    fn test_solve_part1_wide_range() {
+        // Every block of k digits repeated twice, up to 10 digits
+        let expected: u64 = (1..=5)
+            .map(|k| {
+                let (lowest, highest) = (10u64.pow(k - 1), 10u64.pow(k) - 1);
+                (10u64.pow(k) + 1) * (lowest + highest) * (highest - lowest + 1) / 2
+            })
+            .sum();
+
+        assert_eq!(solve_part1("1-9999999999").unwrap(), expected.to_string());
+    }
+
+    #[test]
+    fn test_to_digits() {
+        assert_eq!(to_digits(0, 10), "0");
+        assert_eq!(to_digits(10, 2), "1010");
+        assert_eq!(to_digits(0xbeef, 16), "beef");
+        assert_eq!(to_digits(35, 36), "z");
+    }
+
+    #[test]
+    fn test_repeated_ids_in_other_bases_match_brute_force() {
+        for base in [2, 8, 16] {
+            let mut generated: Vec<u64> =
+                repeated_ids(0, 5000, base).unwrap().map(|r| r.id).collect();
+            generated.sort();
+
+            let expected: Vec<u64> = (0..=5000)
+                .filter(|&x| has_repeated_sequence(x, base))
+                .collect();
+            assert_eq!(generated, expected, "base {}", base);
+        }
+    }
+
+    #[test]
+    fn test_solve_in_other_bases() {
+        // 1010, 1111 and 100100 in binary are 10, 15 and 36
+        assert_eq!(solve_part1_in_base("1000-101010", 2).unwrap(), "61");
+        // Along with 111, 11111 and 101010, which are 7, 31 and 42
+        assert_eq!(solve_part2_in_base("100-101010", 2).unwrap(), "141");
+        // aa, bb, ..., ff in hex
+        assert_eq!(solve_part1_in_base("a0-ff", 16).unwrap(), "1275");
+        assert!(solve_part1_in_base("11-22", 37).is_err());
+        assert!(solve_part1_in_base("12-22", 2).is_err());
+
+        let mut params = Params::default();
+        params.set(Day02::PARAMS, "base", 16).unwrap();
+        assert_eq!(
+            Day02::part1_with(&"a0-ff".to_string(), &params).unwrap(),
+            "1275"
+        );
+    }
+
+    #[test]
+    fn test_analysis_breaks_down_by_repeats() {
+        let analysis = analyse("95-115,998-1012,222220-222224", 10).unwrap();
+
+        assert_eq!(
+            analysis.by_repeats,
+            BTreeMap::from([
+                (
+                    2,
+                    RepeatTotals {
+                        count: 2,
+                        sum: 1109
+                    }
+                ),
+                (
+                    3,
+                    RepeatTotals {
+                        count: 2,
+                        sum: 1110
+                    }
+                ),
+                (
+                    6,
+                    RepeatTotals {
+                        count: 1,
+                        sum: 222222
+                    }
+                ),
+            ])
+        );
+        assert_eq!(analysis.part1().unwrap(), 1109 + 222222);
+        assert_eq!(analysis.sum_where(|repeats| repeats == 3).unwrap(), 1110);
+    }
+
+    #[test]
+    fn test_analysis_part1_matches_is_repeated_twice() {
+        let analysis = analyse("1-2000000", 10).unwrap();
+        let expected: u128 = repeated_ids(1, 2000000, 10)
+            .unwrap()
+            .filter(|r| is_repeated_twice(r.id, 10))
+            .map(|r| r.id as u128)
+            .sum();
+
+        assert_eq!(analysis.part1().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parts_match_generated_ids() {
+        for (start, end, base) in [(1, 2000000, 10), (4, 42, 2), (160, 4095, 16)] {
+            let input = format!("{}-{}", to_digits(start, base), to_digits(end, base));
+            let ids: Vec<u64> = repeated_ids(start, end, base)
+                .unwrap()
+                .map(|r| r.id)
+                .collect();
+            let twice: u128 = ids
+                .iter()
+                .filter(|&&id| is_repeated_twice(id, base))
+                .map(|&id| id as u128)
+                .sum();
+            let all: u128 = ids.iter().map(|&id| id as u128).sum();
+
+            assert_eq!(
+                solve_part1_in_base(&input, base).unwrap(),
+                twice.to_string()
+            );
+            assert_eq!(solve_part2_in_base(&input, base).unwrap(), all.to_string());
+        }
+    }
+
+    #[test]
+    fn test_exports() {
+        let input = "95-115,998-1012".to_string();
+
+        assert_eq!(
+            Day02::export(&input, "ids", &Params::default()).unwrap(),
+            "id,block,repeats\n99,9,2\n111,1,3\n999,9,3\n1010,10,2\n"
+        );
+        assert_eq!(
+            Day02::export(&"222220-222224".to_string(), "ids", &Params::default()).unwrap(),
+            "id,block,repeats\n222222,2,6\n"
+        );
+        assert_eq!(
+            Day02::export(&input, "repeats", &Params::default()).unwrap(),
+            "repeats,count,sum\n2,2,1109\n3,2,1110\n"
+        );
+    }
+
+    #[test]
+    fn test_check_accepts_example() {
+        let input = "11-22,95-115,998-1012\n";
+
+        assert_eq!(Day02::check(input), vec![]);
+    }
+
+    #[test]
+    fn test_check_reports_malformed_and_reversed_ranges() {
+        let input = "11-22,9515,115-95,\n";
+
+        assert_eq!(Day02::check(input).len(), 3);
+    }
+
+    #[test]
+    fn test_check_uses_base_param() {
+        let input = "a-ff\n";
+        let mut params = Params::default();
+
+        assert_eq!(Day02::check_with(input, &params).len(), 1);
+
+        params.set(Day02::PARAMS, "base", 16).unwrap();
+        assert_eq!(Day02::check_with(input, &params), vec![]);
+
+        params.set(Day02::PARAMS, "base", 37).unwrap();
+        assert_eq!(Day02::check_with(input, &params).len(), 1);
+    }
+
+    #[test]
+    fn test_repeated_ids_rejects_bad_bases() {
+        assert!(repeated_ids(1, 100, 1).is_err());
+        assert!(repeated_ids(1, 100, 37).is_err());
+        assert!(repeated_ids(1, 100, 36).is_ok());
+    }
+
+    #[test]
+    fn test_check_reports_multiple_lines() {
+        let input = "11-22\n95-115\n";
+
+        assert_eq!(
+            Day02::check(input),
+            vec![Problem::new(2, "ranges should be on a single line")]
+        );
+    }
+}
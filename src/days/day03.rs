@@ -0,0 +1,268 @@
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::check::{Problem, numbered_lines};
+use crate::solution::{Param, ParamKind, Params, Solution};
+use crate::to_lines;
+use crate::trace;
+use crate::trace::Level;
+
+/// Number of digits picked from each line in part 2, which is also the minimum line length
+const PART2_LENGTH: usize = 12;
+
+const PART1_DIGITS: Param = Param {
+    name: "part1_digits",
+    default: 2,
+    description: "Number of digits picked from each line in part 1",
+    kind: ParamKind::Number,
+};
+
+const PART2_DIGITS: Param = Param {
+    name: "part2_digits",
+    default: PART2_LENGTH,
+    description: "Number of digits picked from each line in part 2",
+    kind: ParamKind::Number,
+};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<String>;
+
+    const PARAMS: &'static [Param] = &[PART1_DIGITS, PART2_DIGITS];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(to_lines(input))
+    }
+
+    fn part1(lines: &Self::Input) -> Result<String> {
+        solve_part1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<String> {
+        solve_part2(lines)
+    }
+
+    fn part1_with(lines: &Self::Input, params: &Params) -> Result<String> {
+        sum_maximal_of_length_n(lines, params.get(&PART1_DIGITS))
+    }
+
+    fn part2_with(lines: &Self::Input, params: &Params) -> Result<String> {
+        sum_maximal_of_length_n(lines, params.get(&PART2_DIGITS))
+    }
+
+    fn check(input: &str) -> Vec<Problem> {
+        let mut problems = vec![];
+
+        for (line_no, line) in numbered_lines(input) {
+            if let Some(c) = line.chars().find(|c| !c.is_ascii_digit()) {
+                problems.push(Problem::new(
+                    line_no,
+                    format!("unexpected character '{}'", c),
+                ));
+            } else if line.len() < PART2_LENGTH {
+                problems.push(Problem::new(
+                    line_no,
+                    format!(
+                        "line should have at least {} digits, found {}",
+                        PART2_LENGTH,
+                        line.len()
+                    ),
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+pub fn solve_part1(lines: &[String]) -> Result<String> {
+    sum_maximal_of_length_n(lines, PART1_DIGITS.default)
+}
+
+pub fn solve_part2(lines: &[String]) -> Result<String> {
+    sum_maximal_of_length_n(lines, PART2_DIGITS.default)
+}
+
+fn sum_maximal_of_length_n(lines: &[String], n: usize) -> Result<String> {
+    let mut sum: u64 = 0;
+
+    for line in lines {
+        let joltage = find_maximal_of_length_n(line, n)?;
+        trace!(Level::Debug, "{}: picked {}", line, joltage);
+        sum = sum
+            .checked_add(joltage)
+            .ok_or_else(|| anyhow!("Sum overflowed when adding {}", joltage))?;
+    }
+
+    Ok(sum.to_string())
+}
+
+fn find_maximal_of_length_n(input: &str, n: usize) -> Result<u64> {
+    if n == 0 {
+        bail!("Should pick at least one digit from line: {}", input);
+    }
+    if let Some(invalid_char) = input.chars().find(|c| !c.is_ascii_digit()) {
+        bail!("Unexpected character '{}' in line: {}", invalid_char, input);
+    }
+    if input.len() < n {
+        bail!(
+            "Line should have at least {} digits, found {}: {}",
+            n,
+            input.len(),
+            input
+        );
+    }
+
+    let mut ith_num_idx: usize = 0;
+    let mut chars: Vec<char> = vec![];
+
+    for i in 0..n {
+        let start = if i == 0 { 0 } else { ith_num_idx + 1 };
+        let end = input.len() - n + i;
+
+        let (ith_num_as_char, next_idx) = find_max_number_from_substring(&input[start..=end]);
+
+        chars.push(ith_num_as_char);
+        ith_num_idx = start + next_idx;
+    }
+
+    chars
+        .iter()
+        .collect::<String>()
+        .parse::<u64>()
+        .with_context(|| format!("Should fit {} digits into a number in line: {}", n, input))
+}
+
+fn find_max_number_from_substring(input: &str) -> (char, usize) {
+    let mut max_number_as_char: char = '0';
+    let mut max_number_idx: usize = 0;
+
+    for (idx, x) in input.chars().enumerate() {
+        if x == '9' {
+            // If we find 9, we are done here.
+            // Can't get any better.
+            return (x, idx);
+        } else if x > max_number_as_char {
+            max_number_as_char = x;
+            max_number_idx = idx;
+        }
+    }
+
+    (max_number_as_char, max_number_idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_maximal_of_length_n_pair_1() {
+        let input = "987654321111111";
+
+        assert_eq!(find_maximal_of_length_n(input, 2).unwrap(), 98);
+    }
+
+    #[test]
+    fn test_find_maximal_of_length_n_pair_2() {
+        let input = "811111111111119";
+
+        assert_eq!(find_maximal_of_length_n(input, 2).unwrap(), 89);
+    }
+
+    #[test]
+    fn test_find_maximal_of_length_n_pair_3() {
+        let input = "234234234234278";
+
+        assert_eq!(find_maximal_of_length_n(input, 2).unwrap(), 78);
+    }
+
+    #[test]
+    fn test_find_maximal_of_length_n_pair_4() {
+        let input = "818181911112111";
+
+        assert_eq!(find_maximal_of_length_n(input, 2).unwrap(), 92);
+    }
+
+    #[test]
+    fn test_solve_part1_counts_correctly() {
+        let lines = vec![
+            "987654321111111".to_string(),
+            "811111111111119".to_string(),
+            "234234234234278".to_string(),
+            "818181911112111".to_string(),
+        ];
+
+        assert_eq!(solve_part1(&lines).unwrap(), "357");
+    }
+
+    #[test]
+    fn test_solve_part2_counts_correctly() {
+        let lines = vec![
+            "987654321111111".to_string(),
+            "811111111111119".to_string(),
+            "234234234234278".to_string(),
+            "818181911112111".to_string(),
+        ];
+
+        assert_eq!(solve_part2(&lines).unwrap(), "3121910778619");
+    }
+
+    #[test]
+    fn test_digits_params() {
+        let lines = vec!["987654321111111".to_string()];
+        let mut params = Params::default();
+        params.set(Day03::PARAMS, "part1_digits", 3).unwrap();
+        params.set(Day03::PARAMS, "part2_digits", 1).unwrap();
+
+        assert_eq!(Day03::part1_with(&lines, &params).unwrap(), "987");
+        assert_eq!(Day03::part2_with(&lines, &params).unwrap(), "9");
+    }
+
+    #[test]
+    fn test_find_maximal_of_length_n_short_line_returns_error() {
+        let input = "98765";
+
+        assert!(find_maximal_of_length_n(input, 12).is_err());
+    }
+
+    #[test]
+    fn test_find_maximal_of_length_n_non_digit_returns_error() {
+        let input = "98a7654321";
+
+        assert!(find_maximal_of_length_n(input, 2).is_err());
+    }
+
+    #[test]
+    fn test_find_maximal_of_length_n_multibyte_char_returns_error() {
+        let input = "9876543210ä";
+
+        assert!(find_maximal_of_length_n(input, 2).is_err());
+    }
+
+    #[test]
+    fn test_find_maximal_of_length_n_too_many_digits_returns_error() {
+        let input = "99999999999999999999999";
+
+        assert!(find_maximal_of_length_n(input, 21).is_err());
+    }
+
+    #[test]
+    fn test_solve_part2_short_line_returns_error() {
+        let lines = vec!["987654321111111".to_string(), "81".to_string()];
+
+        assert!(solve_part2(&lines).is_err());
+    }
+
+    #[test]
+    fn test_check_reports_bad_and_short_lines() {
+        let input = "987654321111111\n8111x1111111119\n\n2342\n";
+
+        assert_eq!(
+            Day03::check(input),
+            vec![
+                Problem::new(2, "unexpected character 'x'"),
+                Problem::new(4, "line should have at least 12 digits, found 4"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::Result;
+
+use crate::check::{Problem, check_grid};
+use crate::solution::{Param, ParamKind, Params, Solution};
+use crate::to_grid;
+use crate::trace;
+use crate::trace::Level;
+
+pub struct Day04;
+
+const THRESHOLD: Param = Param {
+    name: "threshold",
+    default: 4,
+    description: "Rolls with fewer neighbouring rolls than this are accessible",
+    kind: ParamKind::Number,
+};
+
+impl Solution for Day04 {
+    type Input = Vec<Vec<char>>;
+
+    const PARAMS: &'static [Param] = &[THRESHOLD];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(to_grid(input))
+    }
+
+    fn part1(grid: &Self::Input) -> Result<String> {
+        solve_part1(grid)
+    }
+
+    fn part2(grid: &Self::Input) -> Result<String> {
+        solve_part2(grid)
+    }
+
+    fn part1_with(grid: &Self::Input, params: &Params) -> Result<String> {
+        count_accessible(grid, params.get(&THRESHOLD))
+    }
+
+    fn part2_with(grid: &Self::Input, params: &Params) -> Result<String> {
+        count_removable(grid, params.get(&THRESHOLD))
+    }
+
+    fn inspect(grid: &Self::Input, _params: &Params, limit: usize) -> Result<Vec<String>> {
+        Ok(neighbour_counts(grid).take(limit).collect())
+    }
+
+    fn check(input: &str) -> Vec<Problem> {
+        check_grid(input, &['@', '.'])
+    }
+}
+
+const NEIGHBOURING_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+pub fn solve_part1(grid: &[Vec<char>]) -> Result<String> {
+    count_accessible(grid, THRESHOLD.default)
+}
+
+pub fn solve_part2(grid: &[Vec<char>]) -> Result<String> {
+    count_removable(grid, THRESHOLD.default)
+}
+
+fn count_accessible(grid: &[Vec<char>], threshold: usize) -> Result<String> {
+    let mut sum: usize = 0;
+
+    for y in 0..grid.len() {
+        for x in 0..grid[y].len() {
+            if grid[y][x] == '.' {
+                continue;
+            }
+            if find_neighbours(x, y, grid) < threshold {
+                trace!(Level::Trace, "roll at ({}, {}) is accessible", x, y);
+                sum += 1;
+            }
+        }
+    }
+
+    Ok(sum.to_string())
+}
+
+/// Repeatedly remove accessible rolls, counting every roll removed
+fn count_removable(grid: &[Vec<char>], threshold: usize) -> Result<String> {
+    let mut sum: usize = 0;
+
+    // Neighbour count of every roll still in place, `None` for empty or already removed spots
+    let mut neighbours: Vec<Vec<Option<usize>>> = grid
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            (0..row.len())
+                .map(|x| (row[x] != '.').then(|| find_neighbours(x, y, grid)))
+                .collect()
+        })
+        .collect();
+    let mut to_remove: Vec<(usize, usize)> = vec![];
+
+    for (y, row) in neighbours.iter().enumerate() {
+        for (x, count) in row.iter().enumerate() {
+            if count.is_some_and(|count| count < threshold) {
+                to_remove.push((x, y));
+            }
+        }
+    }
+    trace!(
+        Level::Info,
+        "{} rolls accessible initially",
+        to_remove.len()
+    );
+
+    // Removing rolls only ever lowers the neighbour counts, so the end result doesn't depend
+    // on the removal order and each roll needs to be queued at most once: either right away
+    // or when its count drops below the threshold.
+    while let Some((x, y)) = to_remove.pop() {
+        neighbours[y][x] = None;
+        sum += 1;
+        trace!(Level::Trace, "removed roll at ({}, {})", x, y);
+
+        if grid[y][x] != '@' {
+            continue;
+        }
+        for (dx, dy) in NEIGHBOURING_OFFSETS {
+            if let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy))
+                && let Some(Some(count)) = neighbours.get_mut(ny).and_then(|row| row.get_mut(nx))
+            {
+                *count -= 1;
+                if *count + 1 == threshold {
+                    to_remove.push((nx, ny));
+                }
+            }
+        }
+    }
+
+    Ok(sum.to_string())
+}
+
+/// Rows of the grid with every roll replaced by its number of neighbouring rolls
+fn neighbour_counts(grid: &[Vec<char>]) -> impl Iterator<Item = String> {
+    grid.iter().enumerate().map(move |(y, row)| {
+        row.iter()
+            .enumerate()
+            .map(|(x, &spot)| match spot {
+                '.' => '.',
+                _ => char::from(b'0' + find_neighbours(x, y, grid) as u8),
+            })
+            .collect()
+    })
+}
+
+fn find_neighbours(x: usize, y: usize, grid: &[Vec<char>]) -> usize {
+    let mut num_of_neighbours: usize = 0;
+
+    for (dx, dy) in NEIGHBOURING_OFFSETS {
+        if let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy))
+            && ny < grid.len()
+            && nx < grid[ny].len()
+            && grid[ny][nx] == '@'
+        {
+            num_of_neighbours += 1;
+        }
+    }
+
+    num_of_neighbours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_part1_counts_correctly() {
+        let lines = vec![
+            "..@@.@@@@.".to_string(),
+            "@@@.@.@.@@".to_string(),
+            "@@@@@.@.@@".to_string(),
+            "@.@@@@..@.".to_string(),
+            "@@.@@@@.@@".to_string(),
+            ".@@@@@@@.@".to_string(),
+            ".@.@.@.@@@".to_string(),
+            "@.@@@.@@@@".to_string(),
+            ".@@@@@@@@.".to_string(),
+            "@.@.@@@.@.".to_string(),
+        ];
+        let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+
+        assert_eq!(solve_part1(&grid).unwrap(), "13");
+    }
+
+    #[test]
+    fn test_solve_part2_counts_correctly() {
+        let lines = vec![
+            "..@@.@@@@.".to_string(),
+            "@@@.@.@.@@".to_string(),
+            "@@@@@.@.@@".to_string(),
+            "@.@@@@..@.".to_string(),
+            "@@.@@@@.@@".to_string(),
+            ".@@@@@@@.@".to_string(),
+            ".@.@.@.@@@".to_string(),
+            "@.@@@.@@@@".to_string(),
+            ".@@@@@@@@.".to_string(),
+            "@.@.@@@.@.".to_string(),
+        ];
+        let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+
+        assert_eq!(solve_part2(&grid).unwrap(), "43");
+    }
+
+    #[test]
+    fn test_threshold_param() {
+        let grid = to_grid("@@@\n@@@\n.@.\n");
+        let mut params = Params::default();
+
+        params.set(Day04::PARAMS, "threshold", 9).unwrap();
+        assert_eq!(Day04::part1_with(&grid, &params).unwrap(), "7");
+        assert_eq!(Day04::part2_with(&grid, &params).unwrap(), "7");
+
+        params.set(Day04::PARAMS, "threshold", 0).unwrap();
+        assert_eq!(Day04::part1_with(&grid, &params).unwrap(), "0");
+        assert_eq!(Day04::part2_with(&grid, &params).unwrap(), "0");
+    }
+
+    #[test]
+    fn test_inspect_shows_neighbour_counts() {
+        let grid = Day04::parse("@@.\n@.@\n.@@\n").unwrap();
+
+        assert_eq!(
+            Day04::inspect(&grid, &Params::default(), 10).unwrap(),
+            vec!["23.", "3.3", ".32"]
+        );
+        assert_eq!(
+            Day04::inspect(&grid, &Params::default(), 1).unwrap(),
+            vec!["23."]
+        );
+    }
+
+    #[test]
+    fn test_check_reports_ragged_rows() {
+        let input = "..@@.\n@@@.@\n@@\n";
+
+        assert_eq!(
+            Day04::check(input),
+            vec![Problem::new(3, "row has 2 columns, expected 5")]
+        );
+    }
+}
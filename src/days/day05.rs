@@ -0,0 +1,295 @@
+use std::cmp::Ordering;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::check::{Problem, numbered_lines};
+use crate::solution::Solution;
+use crate::to_lines;
+use crate::trace;
+use crate::trace::Level;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    /// Fresh ingredient ID ranges and available ingredient IDs
+    type Input = (Vec<String>, Vec<String>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(to_lines(input)
+            .into_iter()
+            .partition(|line| line.contains('-')))
+    }
+
+    fn part1((ranges, ingredients): &Self::Input) -> Result<String> {
+        solve_part1(ranges, ingredients)
+    }
+
+    fn part2((ranges, _): &Self::Input) -> Result<String> {
+        solve_part2(ranges)
+    }
+
+    fn check(input: &str) -> Vec<Problem> {
+        let mut problems = vec![];
+        let mut num_ranges: usize = 0;
+        let mut seen_ingredient = false;
+
+        for (line_no, line) in numbered_lines(input) {
+            if line.contains('-') {
+                num_ranges += 1;
+                if seen_ingredient {
+                    problems.push(Problem::new(
+                        line_no,
+                        "range should come before the ingredient IDs",
+                    ));
+                }
+                if let Err(err) = parse_and_sort_ranges(&[line.to_string()]) {
+                    problems.push(Problem::new(line_no, format!("{:#}", err)));
+                }
+            } else {
+                seen_ingredient = true;
+                if line.parse::<u64>().is_err() {
+                    problems.push(Problem::new(
+                        line_no,
+                        format!("should be a range or an ingredient ID: {}", line),
+                    ));
+                }
+            }
+        }
+
+        if num_ranges == 0 {
+            problems.push(Problem::new(0, "input has no ranges"));
+        }
+
+        problems
+    }
+}
+
+pub fn solve_part1(ranges: &[String], ingredients: &[String]) -> Result<String> {
+    let parsed_and_sorted_ranges = parse_and_sort_ranges(ranges)?;
+    let merged_ranges = merge_overlapping_ranges(&parsed_and_sorted_ranges);
+    trace_merged_ranges(&parsed_and_sorted_ranges, &merged_ranges);
+    let parsed_ingredients = ingredients
+        .iter()
+        .map(|i| {
+            i.parse::<u64>()
+                .with_context(|| format!("Should be able to parse ingredient: {}", i))
+        })
+        .collect::<Result<Vec<u64>>>()?;
+
+    let mut sum: u64 = 0;
+
+    for ingr in parsed_ingredients {
+        let res = merged_ranges.binary_search_by(|range| {
+            if ingr < range.start {
+                Ordering::Greater
+            } else if ingr > range.end {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        });
+
+        trace!(Level::Debug, "ingredient {}: fresh {}", ingr, res.is_ok());
+        if res.is_ok() {
+            sum += 1;
+        }
+    }
+
+    Ok(sum.to_string())
+}
+
+pub fn solve_part2(ranges: &[String]) -> Result<String> {
+    let parsed_and_sorted_ranges = parse_and_sort_ranges(ranges)?;
+    let merged_ranges = merge_overlapping_ranges(&parsed_and_sorted_ranges);
+    trace_merged_ranges(&parsed_and_sorted_ranges, &merged_ranges);
+
+    let mut sum: u64 = 0;
+
+    for range in merged_ranges {
+        sum = (range.end - range.start)
+            .checked_add(1)
+            .and_then(|len| sum.checked_add(len))
+            .ok_or_else(|| anyhow!("Sum overflowed when adding range {:?}", range))?;
+    }
+
+    Ok(sum.to_string())
+}
+
+fn trace_merged_ranges(ranges: &[Range], merged: &[Range]) {
+    trace!(
+        Level::Info,
+        "merged {} ranges into {}",
+        ranges.len(),
+        merged.len()
+    );
+    for range in merged {
+        trace!(Level::Debug, "merged range {}-{}", range.start, range.end);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Range {
+    start: u64,
+    end: u64,
+}
+
+fn parse_and_sort_ranges(ranges_as_str: &[String]) -> Result<Vec<Range>> {
+    let mut ranges = ranges_as_str
+        .iter()
+        .map(|x| {
+            let (start_str, end_str) = x
+                .split_once('-')
+                .ok_or_else(|| anyhow!("Range should be in the form START-END: {}", x))?;
+            let start = start_str
+                .parse::<u64>()
+                .with_context(|| format!("Should be able to parse range start in: {}", x))?;
+            let end = end_str
+                .parse::<u64>()
+                .with_context(|| format!("Should be able to parse range end in: {}", x))?;
+            if start > end {
+                bail!("Range start should not be greater than its end: {}", x);
+            }
+
+            Ok(Range { start, end })
+        })
+        .collect::<Result<Vec<Range>>>()?;
+
+    ranges.sort();
+
+    Ok(ranges)
+}
+
+fn merge_overlapping_ranges(ranges: &[Range]) -> Vec<Range> {
+    let mut merged_ranges: Vec<Range> = vec![];
+
+    for range in ranges {
+        match merged_ranges.last_mut() {
+            Some(last) if range.start <= last.end => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged_ranges.push(*range),
+        }
+    }
+
+    merged_ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_sort_ranges_sorts_by_start_and_then_by_end() {
+        let input: Vec<String> = vec!["3-5", "10-14", "16-20", "12-18", "10-15"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let sorted_ranges = parse_and_sort_ranges(&input).unwrap();
+        assert_eq!(
+            sorted_ranges,
+            vec![
+                Range { start: 3, end: 5 },
+                Range { start: 10, end: 14 },
+                Range { start: 10, end: 15 },
+                Range { start: 12, end: 18 },
+                Range { start: 16, end: 20 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_overlapping_ranges_merges_correctly() {
+        let ranges = vec![
+            Range { start: 3, end: 5 },
+            Range { start: 10, end: 14 },
+            Range { start: 12, end: 18 },
+            Range { start: 16, end: 20 },
+        ];
+        assert_eq!(
+            merge_overlapping_ranges(&ranges),
+            vec![Range { start: 3, end: 5 }, Range { start: 10, end: 20 }]
+        );
+    }
+
+    #[test]
+    fn test_solve_part1_counts_correctly() {
+        let ranges: Vec<String> = vec!["3-5", "10-14", "16-20", "12-18"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let ingredients: Vec<String> = vec!["1", "5", "8", "11", "17", "32"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(solve_part1(&ranges, &ingredients).unwrap(), "3");
+    }
+
+    #[test]
+    fn test_solve_part2_counts_correctly() {
+        let ranges: Vec<String> = vec!["3-5", "10-14", "16-20", "12-18"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(solve_part2(&ranges).unwrap(), "14");
+    }
+
+    #[test]
+    fn test_parse_and_sort_ranges_missing_dash_returns_error() {
+        let input = vec!["3-5".to_string(), "10".to_string()];
+
+        assert!(parse_and_sort_ranges(&input).is_err());
+    }
+
+    #[test]
+    fn test_parse_and_sort_ranges_reversed_range_returns_error() {
+        let input = vec!["5-3".to_string()];
+
+        assert!(parse_and_sort_ranges(&input).is_err());
+    }
+
+    #[test]
+    fn test_merge_overlapping_ranges_empty_returns_empty() {
+        assert_eq!(merge_overlapping_ranges(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_solve_part1_bad_ingredient_returns_error() {
+        let ranges = vec!["3-5".to_string()];
+        let ingredients = vec!["1".to_string(), "x".to_string()];
+
+        assert!(solve_part1(&ranges, &ingredients).is_err());
+    }
+
+    #[test]
+    fn test_solve_part2_no_ranges_counts_zero() {
+        assert_eq!(solve_part2(&[]).unwrap(), "0");
+    }
+
+    #[test]
+    fn test_solve_part2_full_range_returns_error() {
+        let ranges = vec!["0-18446744073709551615".to_string()];
+
+        assert!(solve_part2(&ranges).is_err());
+    }
+
+    #[test]
+    fn test_check_accepts_example() {
+        let input = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n";
+
+        assert_eq!(Day05::check(input), vec![]);
+    }
+
+    #[test]
+    fn test_check_reports_bad_and_misplaced_lines() {
+        let input = "3-5\n5-3\n\n1\nfoo\n10-14\n";
+
+        assert_eq!(
+            Day05::check(input)
+                .iter()
+                .map(|p| p.line)
+                .collect::<Vec<_>>(),
+            vec![2, 5, 6]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::check::{Problem, numbered_lines};
+use crate::solution::Solution;
+use crate::to_lines;
+use crate::trace;
+use crate::trace::Level;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(to_lines(input))
+    }
+
+    fn part1(lines: &Self::Input) -> Result<String> {
+        solve_part1(lines)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<String> {
+        solve_part2(&mut lines.clone())
+    }
+
+    fn check(input: &str) -> Vec<Problem> {
+        let mut problems = vec![];
+        let lines: Vec<(usize, &str)> = numbered_lines(input).collect();
+
+        let Some(((op_line_no, op_line), num_lines)) = lines.split_last() else {
+            return vec![Problem::new(0, "input is empty")];
+        };
+        if num_lines.is_empty() {
+            problems.push(Problem::new(0, "input should have number lines"));
+        }
+
+        if let Some(c) = op_line.chars().find(|c| !matches!(c, '*' | '+' | ' ')) {
+            problems.push(Problem::new(
+                *op_line_no,
+                format!("unexpected character '{}' in operator line", c),
+            ));
+        }
+        let num_problems = op_line.split_whitespace().count();
+
+        for (line_no, line) in num_lines {
+            if let Some(c) = line.chars().find(|c| !c.is_ascii_digit() && *c != ' ') {
+                problems.push(Problem::new(
+                    *line_no,
+                    format!("unexpected character '{}'", c),
+                ));
+            }
+            let num_numbers = line.split_whitespace().count();
+            if num_numbers != num_problems {
+                problems.push(Problem::new(
+                    *line_no,
+                    format!(
+                        "line has {} numbers, expected {} to match the operators",
+                        num_numbers, num_problems
+                    ),
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+pub fn solve_part1(lines: &[String]) -> Result<String> {
+    let (mut numbers, mut operators) = parse_numbers_operators_part_1(lines)?;
+    let mut sum: u64 = 0;
+
+    'outer: loop {
+        let mut curr_numbers: Vec<u64> = vec![];
+
+        for num_vec in numbers.iter_mut() {
+            if let Some(num) = num_vec.pop() {
+                curr_numbers.push(num);
+            } else {
+                break 'outer;
+            }
+        }
+        if let Some(op) = operators.pop() {
+            sum = checked_add(sum, op.apply(&curr_numbers)?)?;
+        }
+    }
+
+    Ok(sum.to_string())
+}
+
+pub fn solve_part2(lines: &mut [String]) -> Result<String> {
+    let (op_line, num_lines) = lines
+        .split_last_mut()
+        .ok_or_else(|| anyhow!("Input should have an operator line"))?;
+    let mut sum: u64 = 0;
+    let mut curr_numbers: Vec<u64> = vec![];
+
+    'outer: loop {
+        let mut num_as_str = String::new();
+
+        for num_line in num_lines.iter_mut() {
+            if let Some(char) = num_line.pop() {
+                if let Some(num) = char.to_digit(10) {
+                    num_as_str.push_str(&num.to_string());
+                }
+            } else {
+                break 'outer;
+            }
+        }
+
+        if !num_as_str.is_empty() {
+            curr_numbers.push(num_as_str.parse::<u64>()?);
+        }
+
+        if let Some(char) = op_line.pop() {
+            match char {
+                // my poor enum :(
+                '*' => {
+                    sum = checked_add(sum, Op::Product.apply(&curr_numbers)?)?;
+                    curr_numbers = vec![];
+                }
+                '+' => {
+                    sum = checked_add(sum, Op::Sum.apply(&curr_numbers)?)?;
+                    curr_numbers = vec![];
+                }
+                _ => { /* do nothing */ }
+            }
+        }
+    }
+
+    Ok(sum.to_string())
+}
+
+fn parse_numbers_operators_part_1(lines: &[String]) -> Result<(Vec<Vec<u64>>, Vec<Op>)> {
+    let (op_line, num_lines) = lines
+        .split_last()
+        .ok_or_else(|| anyhow!("Input should have an operator line"))?;
+
+    let numbers = num_lines
+        .iter()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|str| {
+                    str.parse::<u64>()
+                        .with_context(|| format!("Bad number {} in line: {}", str, line))
+                })
+                .collect::<Result<Vec<u64>>>()
+        })
+        .collect::<Result<Vec<Vec<u64>>>>()?;
+
+    let operators = op_line
+        .split_whitespace()
+        .map(|str| match str {
+            "*" => Ok(Op::Product),
+            "+" => Ok(Op::Sum),
+            _ => bail!("Bad operator {}", str),
+        })
+        .collect::<Result<Vec<Op>>>()?;
+
+    Ok((numbers, operators))
+}
+
+#[derive(Debug)]
+enum Op {
+    Product,
+    Sum,
+}
+
+impl Op {
+    fn apply(&self, numbers: &[u64]) -> Result<u64> {
+        let result = match self {
+            Op::Product => numbers.iter().try_fold(1u64, |acc, &x| acc.checked_mul(x)),
+            Op::Sum => numbers.iter().try_fold(0u64, |acc, &x| acc.checked_add(x)),
+        };
+
+        let result =
+            result.ok_or_else(|| anyhow!("{:?} overflowed for numbers {:?}", self, numbers))?;
+        trace!(Level::Debug, "{:?} of {:?} = {}", self, numbers, result);
+
+        Ok(result)
+    }
+}
+
+fn checked_add(sum: u64, x: u64) -> Result<u64> {
+    sum.checked_add(x)
+        .ok_or_else(|| anyhow!("Sum overflowed when adding {}", x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_part1_counts_correctly() {
+        let lines: Vec<String> = vec![
+            "123 328  51 64 ",
+            " 45 64  387 23 ",
+            "  6 98  215 314",
+            "*   +   *   +  ",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        assert_eq!(solve_part1(&lines).unwrap(), "4277556");
+    }
+
+    #[test]
+    fn test_solve_part2_counts_correctly() {
+        let mut lines: Vec<String> = vec![
+            "123 328  51 64 ",
+            " 45 64  387 23 ",
+            "  6 98  215 314",
+            "*   +   *   +  ",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        assert_eq!(solve_part2(&mut lines).unwrap(), "3263827");
+    }
+
+    #[test]
+    fn test_solve_part1_empty_input_returns_error() {
+        assert!(solve_part1(&[]).is_err());
+    }
+
+    #[test]
+    fn test_solve_part1_bad_number_returns_error() {
+        let lines: Vec<String> = vec!["12 x4", "+  * "]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert!(solve_part1(&lines).is_err());
+    }
+
+    #[test]
+    fn test_solve_part1_product_overflow_returns_error() {
+        let lines: Vec<String> = vec!["4294967296", "4294967296", "*"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert!(solve_part1(&lines).is_err());
+    }
+
+    #[test]
+    fn test_solve_part2_empty_input_returns_error() {
+        assert!(solve_part2(&mut []).is_err());
+    }
+
+    #[test]
+    fn test_check_accepts_example() {
+        let input = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+
+        assert_eq!(Day06::check(input), vec![]);
+    }
+
+    #[test]
+    fn test_check_reports_bad_lines() {
+        let input = "123 328\n 45 x4 \n  6\n*   -  \n";
+
+        assert_eq!(
+            Day06::check(input)
+                .iter()
+                .map(|p| p.line)
+                .collect::<Vec<_>>(),
+            vec![4, 2, 3]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, anyhow, bail};
+
+use crate::check::{Problem, check_grid, numbered_lines};
+use crate::solution::Solution;
+use crate::to_grid;
+use crate::trace;
+use crate::trace::Level;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(to_grid(input))
+    }
+
+    fn part1(grid: &Self::Input) -> Result<String> {
+        solve_part1(grid)
+    }
+
+    fn part2(grid: &Self::Input) -> Result<String> {
+        solve_part2(grid)
+    }
+
+    fn check(input: &str) -> Vec<Problem> {
+        let mut problems = check_grid(input, &['.', '^', 'S']);
+        let mut num_starts: usize = 0;
+
+        for (idx, (line_no, line)) in numbered_lines(input).enumerate() {
+            for _ in line.matches('S') {
+                num_starts += 1;
+                if idx != 0 {
+                    problems.push(Problem::new(
+                        line_no,
+                        "start 'S' should be on the first row",
+                    ));
+                }
+            }
+        }
+
+        if num_starts != 1 {
+            problems.push(Problem::new(
+                0,
+                format!("input should have exactly one 'S', found {}", num_starts),
+            ));
+        }
+
+        problems
+    }
+}
+
+pub fn solve_part1(grid: &[Vec<char>]) -> Result<String> {
+    let mut num_splits: usize = 0;
+
+    let mut beam_pos: HashSet<usize> = HashSet::new();
+    let start_x = find_start(grid)?;
+    beam_pos.insert(start_x);
+
+    for y in 1..grid.len() {
+        let mut new_pos = Vec::new();
+
+        for x in beam_pos.drain() {
+            match cell_at(grid, x, y)? {
+                '^' => {
+                    if let Some(left_x) = x.checked_sub(1) {
+                        new_pos.push(left_x);
+                    }
+                    if x + 1 < grid[y].len() {
+                        new_pos.push(x + 1);
+                    }
+                    num_splits += 1;
+                }
+                '.' => {
+                    new_pos.push(x);
+                }
+                c => bail!("Unknown character {} at row {}, column {}", c, y + 1, x + 1),
+            }
+        }
+        beam_pos.extend(new_pos);
+        trace!(
+            Level::Debug,
+            "row {}: beams at {:?}, {} splits so far",
+            y + 1,
+            sorted_columns(beam_pos.iter().copied()),
+            num_splits
+        );
+    }
+
+    Ok(num_splits.to_string())
+}
+
+pub fn solve_part2(grid: &[Vec<char>]) -> Result<String> {
+    // key: x-pos, value: number of distinct paths that reached it
+    let mut paths: HashMap<usize, usize> = HashMap::new();
+    let start_x = find_start(grid)?;
+    paths.insert(start_x, 1);
+
+    for y in 1..grid.len() {
+        let mut new_paths: HashMap<usize, usize> = HashMap::new();
+
+        for (&x, &count) in &paths {
+            match cell_at(grid, x, y)? {
+                '^' => {
+                    if let Some(left_x) = x.checked_sub(1) {
+                        add_paths(&mut new_paths, left_x, count)?;
+                    }
+                    if x + 1 < grid[y].len() {
+                        add_paths(&mut new_paths, x + 1, count)?;
+                    }
+                }
+                '.' => {
+                    add_paths(&mut new_paths, x, count)?;
+                }
+                c => bail!("Unknown character {} at row {}, column {}", c, y + 1, x + 1),
+            }
+        }
+
+        paths = new_paths;
+        trace!(
+            Level::Debug,
+            "row {}: paths per column {:?}",
+            y + 1,
+            sorted_columns(paths.iter().map(|(&x, &count)| (x, count)))
+        );
+    }
+
+    let total = paths
+        .values()
+        .try_fold(0usize, |acc, &count| acc.checked_add(count))
+        .ok_or_else(|| anyhow!("Number of paths overflowed"))?;
+
+    Ok(total.to_string())
+}
+
+/// Beam columns in order, for trace output
+fn sorted_columns<T: Ord>(columns: impl Iterator<Item = T>) -> Vec<T> {
+    let mut columns: Vec<T> = columns.collect();
+    columns.sort();
+    columns
+}
+
+fn find_start(grid: &[Vec<char>]) -> Result<usize> {
+    grid.first()
+        .ok_or_else(|| anyhow!("Grid should not be empty"))?
+        .iter()
+        .position(|char| *char == 'S')
+        .ok_or_else(|| anyhow!("Start not found"))
+}
+
+fn cell_at(grid: &[Vec<char>], x: usize, y: usize) -> Result<char> {
+    grid[y].get(x).copied().ok_or_else(|| {
+        anyhow!(
+            "Row {} is too short ({} columns) for a beam at column {}",
+            y + 1,
+            grid[y].len(),
+            x + 1
+        )
+    })
+}
+
+fn add_paths(paths: &mut HashMap<usize, usize>, x: usize, count: usize) -> Result<()> {
+    let entry = paths.entry(x).or_insert(0);
+    *entry = entry
+        .checked_add(count)
+        .ok_or_else(|| anyhow!("Number of paths overflowed at column {}", x + 1))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_part1_counts_correctly() {
+        let lines = vec![
+            ".......S.......",
+            "...............",
+            ".......^.......",
+            "...............",
+            "......^.^......",
+            "...............",
+            ".....^.^.^.....",
+            "...............",
+            "....^.^...^....",
+            "...............",
+            "...^.^...^.^...",
+            "...............",
+            "..^...^.....^..",
+            "...............",
+            ".^.^.^.^.^...^.",
+            "...............",
+        ];
+        let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+        assert_eq!(solve_part1(&grid).unwrap(), "21");
+    }
+
+    #[test]
+    fn test_solve_part2_counts_correctly() {
+        let lines = vec![
+            ".......S.......",
+            "...............",
+            ".......^.......",
+            "...............",
+            "......^.^......",
+            "...............",
+            ".....^.^.^.....",
+            "...............",
+            "....^.^...^....",
+            "...............",
+            "...^.^...^.^...",
+            "...............",
+            "..^...^.....^..",
+            "...............",
+            ".^.^.^.^.^...^.",
+            "...............",
+        ];
+        let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+        assert_eq!(solve_part2(&grid).unwrap(), "40");
+    }
+
+    #[test]
+    fn test_solve_part1_empty_grid_returns_error() {
+        assert!(solve_part1(&[]).is_err());
+    }
+
+    #[test]
+    fn test_solve_part1_ragged_rows_returns_error() {
+        let lines = ["...S", "....", ".."];
+        let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+
+        assert!(solve_part1(&grid).is_err());
+    }
+
+    #[test]
+    fn test_solve_part2_empty_grid_returns_error() {
+        assert!(solve_part2(&[]).is_err());
+    }
+
+    #[test]
+    fn test_solve_part2_ragged_rows_returns_error() {
+        let lines = ["...S", "....", ".."];
+        let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+
+        assert!(solve_part2(&grid).is_err());
+    }
+
+    #[test]
+    fn test_check_accepts_example() {
+        let input = "..S..\n.....\n..^..\n";
+
+        assert_eq!(Day07::check(input), vec![]);
+    }
+
+    #[test]
+    fn test_check_reports_misplaced_and_extra_starts() {
+        let input = ".....\n..S..\n..^S.\n";
+
+        assert_eq!(
+            Day07::check(input),
+            vec![
+                Problem::new(2, "start 'S' should be on the first row"),
+                Problem::new(3, "start 'S' should be on the first row"),
+                Problem::new(0, "input should have exactly one 'S', found 2"),
+            ]
+        );
+    }
+}
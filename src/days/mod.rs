@@ -0,0 +1,63 @@
+use anyhow::Result;
+
+use crate::check::Problem;
+use crate::repl::{self, Loaded};
+use crate::runner::{self, DayReport};
+use crate::solution::{Param, Params, Solution};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+
+/// Type-erased entry point to a single day's `Solution`
+pub struct Day {
+    pub day: u8,
+    pub version: u32,
+    /// Source code of the day's module
+    pub source: &'static str,
+    pub check: fn(&str, &Params) -> Vec<Problem>,
+    pub solve: fn(&str, &[u8], bool, &Params) -> Result<DayReport>,
+    /// Tunable constants of the solver
+    pub params: &'static [Param],
+    /// Parse an input to be solved later, possibly with different parameters
+    pub load: fn(&str) -> Result<Box<dyn Loaded>>,
+    /// Names of the extra outputs of the solver
+    pub exports: &'static [&'static str],
+    pub export: fn(&str, &str, &Params) -> Result<String>,
+}
+
+impl Day {
+    const fn new<S: Solution + 'static>(day: u8, source: &'static str) -> Self {
+        Day {
+            day,
+            version: S::VERSION,
+            source,
+            check: S::check_with,
+            solve: runner::solve::<S>,
+            params: S::PARAMS,
+            load: repl::load::<S>,
+            exports: S::EXPORTS,
+            export: runner::export::<S>,
+        }
+    }
+}
+
+/// All available days in order
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(1, include_str!("day01.rs")),
+    Day::new::<day02::Day02>(2, include_str!("day02.rs")),
+    Day::new::<day03::Day03>(3, include_str!("day03.rs")),
+    Day::new::<day04::Day04>(4, include_str!("day04.rs")),
+    Day::new::<day05::Day05>(5, include_str!("day05.rs")),
+    Day::new::<day06::Day06>(6, include_str!("day06.rs")),
+    Day::new::<day07::Day07>(7, include_str!("day07.rs")),
+];
+
+/// Find a day by its number
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
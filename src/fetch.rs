@@ -0,0 +1,494 @@
+//! Downloading missing puzzle inputs. Plain `http://` URLs are fetched directly, which is what
+//! the tests use with a local mock server; `https://` URLs are handed to `curl`, which has to be
+//! installed and on the `PATH`.
+
+use std::fs;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+/// Base URL of the puzzles, overridable with `--base-url` or `AOC_BASE_URL`
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2025";
+/// File holding the session token when `AOC_SESSION` isn't set
+pub const DEFAULT_SESSION_FILE: &str = ".aoc-session";
+/// Time to wait between two requests to the server
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+const USER_AGENT: &str = "advent_of_code_2025 input fetcher";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where and how inputs are downloaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchConfig {
+    pub base_url: String,
+    /// Session token, usually the value of `AOC_SESSION`
+    pub session: Option<String>,
+    /// File the session token is read from when `session` isn't set, only once an input
+    /// actually has to be downloaded
+    pub session_file: PathBuf,
+    /// Directory the inputs are written to
+    pub dir: PathBuf,
+    /// Minimum time between two requests
+    pub interval: Duration,
+    /// Download inputs again even if they already exist
+    pub force: bool,
+}
+
+/// What happened to a single day's input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input file already existed, nothing was downloaded
+    Cached,
+    /// The input was downloaded, this many bytes
+    Downloaded(usize),
+}
+
+/// Downloads inputs one at a time, waiting between requests
+pub struct Fetcher {
+    config: FetchConfig,
+    limiter: RateLimiter,
+    /// Session token once it was needed
+    session: Option<String>,
+}
+
+impl Fetcher {
+    pub fn new(config: FetchConfig) -> Self {
+        let limiter = RateLimiter::new(config.interval);
+        Fetcher {
+            config,
+            limiter,
+            session: None,
+        }
+    }
+
+    /// The session token, read the first time it's needed
+    fn session(&mut self) -> Result<String> {
+        if self.session.is_none() {
+            self.session = Some(read_session(
+                self.config.session.clone(),
+                &self.config.session_file,
+            )?);
+        }
+
+        Ok(self.session.clone().unwrap_or_default())
+    }
+
+    fn path(&self, day: u8) -> PathBuf {
+        self.config.dir.join(format!("day{:0>2}.txt", day))
+    }
+
+    /// Download the input of `day` unless it already exists
+    pub fn fetch_day(&mut self, day: u8) -> Result<Fetched> {
+        let path = self.path(day);
+        if !self.config.force && path.exists() {
+            return Ok(Fetched::Cached);
+        }
+
+        let url = format!(
+            "{}/day/{}/input",
+            self.config.base_url.trim_end_matches('/'),
+            day
+        );
+        let session = self.session()?;
+        self.limiter.wait();
+        let input = http_get(&url, &session)?;
+        if input.trim().is_empty() {
+            bail!("Server returned an empty input for day {}", day);
+        }
+
+        fs::create_dir_all(&self.config.dir).with_context(|| {
+            format!("Could not create directory: {}", self.config.dir.display())
+        })?;
+        // Write to a temporary file first so that an interrupted download never looks complete
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, &input)
+            .with_context(|| format!("Could not write file: {}", partial.display()))?;
+        fs::rename(&partial, &path)
+            .with_context(|| format!("Could not write file: {}", path.display()))?;
+
+        Ok(Fetched::Downloaded(input.len()))
+    }
+}
+
+/// Enforces a minimum interval between consecutive calls to `wait`
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration) -> Self {
+        RateLimiter {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Sleep until at least `interval` has passed since the previous call
+    fn wait(&mut self) {
+        if let Some(last) = self.last {
+            let elapsed = last.elapsed();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+        self.last = Some(Instant::now());
+    }
+}
+
+/// The session token from `env`, the value of `AOC_SESSION`, or else from the file at `path`
+pub fn read_session(env: Option<String>, path: &Path) -> Result<String> {
+    let session = match env {
+        Some(session) => session,
+        None => fs::read_to_string(path).with_context(|| {
+            format!(
+                "Set AOC_SESSION or put the session cookie in {}",
+                path.display()
+            )
+        })?,
+    };
+    let session = session.trim();
+    if session.is_empty() {
+        bail!("Session token is empty");
+    }
+    // The token is written into the Cookie header as is, a line break would end the header
+    if session.chars().any(char::is_control) {
+        bail!("Session token should not contain control characters such as line breaks");
+    }
+
+    Ok(session.to_string())
+}
+
+fn http_get(url: &str, session: &str) -> Result<String> {
+    if url.starts_with("https://") {
+        curl_get(url, session)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        plain_get(rest, session).with_context(|| format!("Could not fetch {}", url))
+    } else {
+        bail!(
+            "Base URL should start with http:// or https://, got {}",
+            url
+        )
+    }
+}
+
+/// GET over a plain TCP connection, `rest` being the URL without its scheme
+fn plain_get(rest: &str, session: &str) -> Result<String> {
+    let (host, path) = rest
+        .split_once('/')
+        .map_or((rest, "/".to_string()), |(host, path)| {
+            (host, format!("/{}", path))
+        });
+    let addr = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut stream = TcpStream::connect(&addr)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n\r\n",
+        path, host, USER_AGENT, session
+    )?;
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response)?;
+    let response = String::from_utf8(response).context("Response should be UTF-8")?;
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| anyhow!("Response has no body"))?;
+
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| anyhow!("Malformed status line"))?;
+    if head
+        .lines()
+        .any(|line| line.to_ascii_lowercase().starts_with("transfer-encoding:"))
+    {
+        bail!("Chunked responses are not supported");
+    }
+    check_status(status, body)?;
+
+    Ok(body.to_string())
+}
+
+fn curl_get(url: &str, session: &str) -> Result<String> {
+    // The cookie is passed on stdin to keep the token out of the process list
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--location"])
+        .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--header", "@-", "--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(curl_error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={}", session)?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "curl failed to fetch {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let output = String::from_utf8(output.stdout).context("Response should be UTF-8")?;
+    let (body, status) = output
+        .rsplit_once('\n')
+        .ok_or_else(|| anyhow!("Missing status code in curl output"))?;
+    check_status(
+        status.parse().context("Malformed status code from curl")?,
+        body,
+    )?;
+
+    Ok(body.to_string())
+}
+
+fn curl_error(err: io::Error) -> anyhow::Error {
+    if err.kind() == ErrorKind::NotFound {
+        anyhow!(
+            "curl is needed for https URLs but was not found, install it or use an http:// base URL"
+        )
+    } else {
+        anyhow!(err).context("Could not run curl, which is needed for https URLs")
+    }
+}
+
+fn check_status(status: u16, body: &str) -> Result<()> {
+    match status {
+        200 => Ok(()),
+        400 | 401 | 403 => bail!("Server rejected the session token (status {})", status),
+        404 => bail!("Input is not available (yet?), status 404"),
+        _ => bail!(
+            "Server responded with status {}: {}",
+            status,
+            body.lines().next().unwrap_or_default()
+        ),
+    }
+}
+
+/// Download the inputs of `days` that are missing, reporting each day on its own line
+pub fn fetch(days: &[u8], config: FetchConfig) -> Result<()> {
+    let mut fetcher = Fetcher::new(config);
+    let mut failures = 0;
+
+    for &day in days {
+        match fetcher.fetch_day(day) {
+            Ok(Fetched::Cached) => println!("Day {:0>2}: already downloaded", day),
+            Ok(Fetched::Downloaded(bytes)) => {
+                println!("Day {:0>2}: downloaded {} bytes", day, bytes)
+            }
+            Err(err) => {
+                println!("Day {:0>2}: error: {:#}", day, err);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} day(s) could not be fetched", failures);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::{SocketAddr, TcpListener};
+    use std::sync::mpsc::{self, Receiver};
+
+    /// Serve `responses` in order on a free localhost port,
+    /// sending the head of each request received
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (SocketAddr, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut head = String::new();
+                while reader.read_line(&mut head).unwrap() > 2 {}
+                sender.send(head).unwrap();
+
+                write!(
+                    &stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (addr, receiver)
+    }
+
+    fn config(addr: SocketAddr, dir: &str) -> FetchConfig {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", dir, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        FetchConfig {
+            base_url: format!("http://{}/2025/", addr),
+            session: Some("secret".to_string()),
+            session_file: dir.join("session"),
+            dir,
+            interval: Duration::ZERO,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn test_fetch_downloads_missing_inputs_only() {
+        let (addr, requests) = mock_server(vec![(200, "L68\nR48\n")]);
+        let config = config(addr, "missing");
+        fs::create_dir_all(&config.dir).unwrap();
+        fs::write(config.dir.join("day02.txt"), "11-22\n").unwrap();
+        let dir = config.dir.clone();
+        let mut fetcher = Fetcher::new(config);
+
+        assert_eq!(fetcher.fetch_day(1).unwrap(), Fetched::Downloaded(8));
+        assert_eq!(fetcher.fetch_day(2).unwrap(), Fetched::Cached);
+        assert_eq!(fetcher.fetch_day(1).unwrap(), Fetched::Cached);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2025/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(requests.try_recv().is_err());
+        assert_eq!(
+            fs::read_to_string(dir.join("day01.txt")).unwrap(),
+            "L68\nR48\n"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_force_downloads_again() {
+        let (addr, requests) = mock_server(vec![(200, "new\n")]);
+        let mut config = config(addr, "force");
+        config.force = true;
+        fs::create_dir_all(&config.dir).unwrap();
+        fs::write(config.dir.join("day03.txt"), "old\n").unwrap();
+        let dir = config.dir.clone();
+
+        assert_eq!(
+            Fetcher::new(config).fetch_day(3).unwrap(),
+            Fetched::Downloaded(4)
+        );
+        assert!(
+            requests
+                .recv()
+                .unwrap()
+                .starts_with("GET /2025/day/3/input")
+        );
+        assert_eq!(fs::read_to_string(dir.join("day03.txt")).unwrap(), "new\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_reports_errors_without_writing() {
+        let (addr, _requests) = mock_server(vec![(404, "Not found"), (400, "Bad"), (200, "\n")]);
+        let config = config(addr, "errors");
+        let dir = config.dir.clone();
+        let mut fetcher = Fetcher::new(config);
+
+        assert!(format!("{:#}", fetcher.fetch_day(4).unwrap_err()).contains("not available"));
+        assert!(format!("{:#}", fetcher.fetch_day(4).unwrap_err()).contains("session token"));
+        assert!(format!("{:#}", fetcher.fetch_day(4).unwrap_err()).contains("empty input"));
+        assert!(!dir.join("day04.txt").exists());
+    }
+
+    #[test]
+    fn test_fetch_reads_session_only_when_downloading() {
+        let (addr, requests) = mock_server(vec![(200, "new\n")]);
+        let mut config = config(addr, "session");
+        config.session = None;
+        fs::create_dir_all(&config.dir).unwrap();
+        fs::write(config.dir.join("day02.txt"), "11-22\n").unwrap();
+        let dir = config.dir.clone();
+        let mut fetcher = Fetcher::new(config);
+
+        assert_eq!(fetcher.fetch_day(2).unwrap(), Fetched::Cached);
+        assert!(format!("{:#}", fetcher.fetch_day(3).unwrap_err()).contains("AOC_SESSION"));
+        assert!(requests.try_recv().is_err());
+
+        fs::write(dir.join("session"), "from-file\n").unwrap();
+        assert_eq!(fetcher.fetch_day(3).unwrap(), Fetched::Downloaded(4));
+        assert!(
+            requests
+                .recv()
+                .unwrap()
+                .contains("Cookie: session=from-file\r\n")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_rate_limiter_spaces_requests() {
+        let mut limiter = RateLimiter::new(Duration::from_millis(30));
+        let start = Instant::now();
+
+        limiter.wait();
+        limiter.wait();
+        limiter.wait();
+
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[test]
+    fn test_read_session_prefers_env_and_trims() {
+        let path = std::env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        fs::write(&path, "from-file\n").unwrap();
+
+        assert_eq!(
+            read_session(Some("env ".to_string()), &path).unwrap(),
+            "env"
+        );
+        assert_eq!(read_session(None, &path).unwrap(), "from-file");
+        assert!(read_session(Some(" ".to_string()), &path).is_err());
+        fs::remove_file(&path).unwrap();
+        assert!(read_session(None, &path).is_err());
+    }
+
+    #[test]
+    fn test_read_session_rejects_control_characters() {
+        let path = std::env::temp_dir().join("aoc-session-unused");
+
+        for session in ["abc\r\nX-Injected: 1", "abc\ndef", "a\tb", "a\0b"] {
+            let err = read_session(Some(session.to_string()), &path).unwrap_err();
+            assert!(err.to_string().contains("control characters"));
+        }
+        assert_eq!(
+            read_session(Some("abc\n".to_string()), &path).unwrap(),
+            "abc"
+        );
+    }
+
+    #[test]
+    fn test_curl_error_explains_missing_curl() {
+        let missing = curl_error(io::Error::from(ErrorKind::NotFound));
+        assert!(missing.to_string().contains("was not found"));
+
+        let denied = curl_error(io::Error::from(ErrorKind::PermissionDenied));
+        assert!(format!("{:#}", denied).starts_with("Could not run curl"));
+    }
+}
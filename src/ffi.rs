@@ -0,0 +1,156 @@
+//! C ABI of the solvers, declared in `include/aoc.h`. Every day and part has its own function
+//! taking the input as a buffer of UTF-8 bytes and returning a status code. The answer, or the
+//! error message when solving failed, is written to `out` as a string that the caller owns and
+//! releases with `aoc_free_string`. No function unwinds into the caller.
+
+use std::ffi::{CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+
+use anyhow::{Result, anyhow};
+
+use crate::days::find_day;
+use crate::solution::Params;
+
+/// The answer was written to `out`
+pub const AOC_OK: i32 = 0;
+/// `input` or `out` was null, nothing was written to `out`
+pub const AOC_ERR_NULL: i32 = 1;
+/// The input isn't valid UTF-8, an error message was written to `out`
+pub const AOC_ERR_UTF8: i32 = 2;
+/// Parsing or solving failed, an error message was written to `out`
+pub const AOC_ERR_SOLVE: i32 = 3;
+/// The solver panicked, an error message was written to `out`
+pub const AOC_ERR_PANIC: i32 = 4;
+
+/// Solve a part and write the answer or the error message to `out`.
+///
+/// # Safety
+///
+/// `input` must be valid for reading `len` bytes unless `len` is 0,
+/// and `out` must be valid for writing a pointer.
+unsafe fn solve_c(day: u8, part: u8, input: *const u8, len: usize, out: *mut *mut c_char) -> i32 {
+    if out.is_null() || (input.is_null() && len > 0) {
+        return AOC_ERR_NULL;
+    }
+    let bytes = if len == 0 {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(input, len) }
+    };
+
+    let (status, message) = match std::str::from_utf8(bytes) {
+        Ok(input) => match panic::catch_unwind(AssertUnwindSafe(|| solve(day, part, input))) {
+            Ok(Ok(answer)) => (AOC_OK, answer),
+            Ok(Err(err)) => (AOC_ERR_SOLVE, format!("{:#}", err)),
+            Err(_) => (AOC_ERR_PANIC, format!("Day {} part {} panicked", day, part)),
+        },
+        Err(err) => (AOC_ERR_UTF8, format!("Input should be UTF-8: {}", err)),
+    };
+    unsafe { *out = to_c_string(message) };
+
+    status
+}
+
+fn solve(day: u8, part: u8, input: &str) -> Result<String> {
+    let day = find_day(day).ok_or_else(|| anyhow!("Day {} is not solved yet", day))?;
+    let mut report = (day.solve)(input, &[part], false, &Params::default())?;
+
+    report
+        .parts
+        .pop()
+        .ok_or_else(|| anyhow!("Part {} was not solved", part))?
+        .answer
+}
+
+/// Owned C string of `s`, with interior NUL bytes dropped
+fn to_c_string(s: String) -> *mut c_char {
+    let s = CString::new(s.replace('\0', "")).unwrap_or_default();
+    s.into_raw()
+}
+
+/// Release a string returned by one of the solver functions. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that wasn't freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Define the exported function of a single day and part
+macro_rules! export_part {
+    ($name:ident, $day:expr, $part:expr) => {
+        /// # Safety
+        ///
+        /// `input` must be valid for reading `len` bytes unless `len` is 0,
+        /// and `out` must be valid for writing a pointer.
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $name(input: *const u8, len: usize, out: *mut *mut c_char) -> i32 {
+            unsafe { solve_c($day, $part, input, len, out) }
+        }
+    };
+}
+
+export_part!(aoc_day01_part1, 1, 1);
+export_part!(aoc_day01_part2, 1, 2);
+export_part!(aoc_day02_part1, 2, 1);
+export_part!(aoc_day02_part2, 2, 2);
+export_part!(aoc_day03_part1, 3, 1);
+export_part!(aoc_day03_part2, 3, 2);
+export_part!(aoc_day04_part1, 4, 1);
+export_part!(aoc_day04_part2, 4, 2);
+export_part!(aoc_day05_part1, 5, 1);
+export_part!(aoc_day05_part2, 5, 2);
+export_part!(aoc_day06_part1, 6, 1);
+export_part!(aoc_day06_part2, 6, 2);
+export_part!(aoc_day07_part1, 7, 1);
+export_part!(aoc_day07_part2, 7, 2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+    use std::ptr;
+
+    /// Call `f` with `input`, returning the status and the string written to `out`
+    fn call(
+        f: unsafe extern "C" fn(*const u8, usize, *mut *mut c_char) -> i32,
+        input: &[u8],
+    ) -> (i32, String) {
+        let mut out = ptr::null_mut();
+        let status = unsafe { f(input.as_ptr(), input.len(), &mut out) };
+        let message = unsafe { CStr::from_ptr(out) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { aoc_free_string(out) };
+
+        (status, message)
+    }
+
+    #[test]
+    fn test_solves_through_c_abi() {
+        let input = b"L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+        assert_eq!(call(aoc_day01_part1, input), (AOC_OK, "3".to_string()));
+        assert_eq!(call(aoc_day01_part2, input), (AOC_OK, "6".to_string()));
+    }
+
+    #[test]
+    fn test_reports_errors_through_c_abi() {
+        let (status, message) = call(aoc_day01_part1, b"X1\n");
+        assert_eq!(status, AOC_ERR_SOLVE);
+        assert!(!message.is_empty());
+
+        assert_eq!(call(aoc_day01_part1, b"\xff").0, AOC_ERR_UTF8);
+
+        let mut out = ptr::null_mut();
+        assert_eq!(
+            unsafe { aoc_day01_part1(ptr::null(), 3, &mut out) },
+            AOC_ERR_NULL
+        );
+        assert!(out.is_null());
+    }
+}
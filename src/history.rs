@@ -0,0 +1,390 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, bail};
+
+use crate::bench::bench_part;
+use crate::days::Day;
+use crate::read_input_raw;
+
+/// Default path of the benchmark history, relative to the working directory
+pub const DEFAULT_HISTORY_PATH: &str = "bench_history.csv";
+
+const HEADER: &str = "run,timestamp,commit,day,part,median_ns";
+
+/// Median of a single day and part in a recorded benchmark run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Increasing number identifying the run the record belongs to
+    pub run: u32,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub commit: String,
+    pub day: u8,
+    pub part: u8,
+    pub median: Duration,
+}
+
+impl Record {
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.run,
+            self.timestamp,
+            self.commit,
+            self.day,
+            self.part,
+            self.median.as_nanos()
+        )
+    }
+
+    fn from_csv(line: &str) -> Result<Self> {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [run, timestamp, commit, day, part, median_ns] = fields[..] else {
+            bail!("History line should have 6 fields: {}", line);
+        };
+        let parse_err = || format!("Should be able to parse history line: {}", line);
+
+        Ok(Record {
+            run: run.parse().with_context(parse_err)?,
+            timestamp: timestamp.parse().with_context(parse_err)?,
+            commit: commit.to_string(),
+            day: day.parse().with_context(parse_err)?,
+            part: part.parse().with_context(parse_err)?,
+            median: Duration::from_nanos(median_ns.parse().with_context(parse_err)?),
+        })
+    }
+}
+
+/// Read every record of the history file, an empty history if the file doesn't exist
+pub fn read_history(path: &Path) -> Result<Vec<Record>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Could not read file: {}", path.display()))?;
+
+    content
+        .lines()
+        .skip(1)
+        .filter(|line| !line.is_empty())
+        .map(Record::from_csv)
+        .collect()
+}
+
+/// Append records to the history file, creating it with a header if needed
+pub fn append_history(path: &Path, records: &[Record]) -> Result<()> {
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Could not open file: {}", path.display()))?;
+
+    let mut content = String::new();
+    if is_new {
+        content.push_str(HEADER);
+        content.push('\n');
+    }
+    for record in records {
+        content.push_str(&record.to_csv());
+        content.push('\n');
+    }
+
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Could not write file: {}", path.display()))
+}
+
+/// Benchmark both parts of the given days and append their medians to the history as a new run
+pub fn record_bench(days: &[&Day], samples: usize, path: &Path) -> Result<()> {
+    let history = read_history(path)?;
+    let run = history.iter().map(|r| r.run).max().map_or(1, |run| run + 1);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let commit = current_commit();
+
+    let mut records = vec![];
+
+    for day in days {
+        let input = match read_input_raw(day.day) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("Day {:0>2}: skipped: {:#}", day.day, err);
+                continue;
+            }
+        };
+
+        for part in [1, 2] {
+            match bench_part(day, &input, part, samples) {
+                Ok(bench) => {
+                    println!(
+                        "Day {:0>2} part {}: {:.1?} (median of {})",
+                        day.day,
+                        part,
+                        bench.median(),
+                        samples
+                    );
+                    records.push(Record {
+                        run,
+                        timestamp,
+                        commit: commit.clone(),
+                        day: day.day,
+                        part,
+                        median: bench.median(),
+                    });
+                }
+                Err(err) => println!("Day {:0>2} part {}: error: {:#}", day.day, part, err),
+            }
+        }
+    }
+
+    if records.is_empty() {
+        bail!("Nothing was benchmarked");
+    }
+    append_history(path, &records)?;
+    println!(
+        "Recorded run {} at commit {} in {}",
+        run,
+        commit,
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Short hash of the current git commit, marked dirty if there are uncommitted changes
+fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", hash)
+        }
+        Some(hash) => hash,
+        None => "unknown".to_string(),
+    }
+}
+
+/// Change of a single day and part between the baseline and the latest run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub latest: Duration,
+    /// Relative change, e.g. `0.25` for 25% slower
+    pub change: f64,
+    /// Slower than the threshold allows
+    pub regressed: bool,
+}
+
+/// Compare the latest run with the run before it, or with the earlier run numbered
+/// `baseline`, or else the latest earlier run of the one commit starting with `baseline`.
+/// Returns the compared run numbers and the changes.
+pub fn compare_runs(
+    history: &[Record],
+    baseline: Option<&str>,
+    threshold: f64,
+) -> Result<(u32, u32, Vec<Comparison>)> {
+    let Some(latest_run) = history.iter().map(|r| r.run).max() else {
+        bail!("History is empty, record a run first");
+    };
+    let earlier = history.iter().filter(|r| r.run < latest_run);
+    let baseline_run = match baseline {
+        Some(name) => find_baseline(earlier, name)?,
+        None => earlier
+            .map(|r| r.run)
+            .max()
+            .context("History has only one run, nothing to compare with")?,
+    };
+
+    let comparisons = history
+        .iter()
+        .filter(|r| r.run == latest_run)
+        .filter_map(|latest| {
+            let base = history
+                .iter()
+                .find(|r| r.run == baseline_run && r.day == latest.day && r.part == latest.part)?;
+            let change = latest.median.as_secs_f64() / base.median.as_secs_f64().max(1e-9) - 1.0;
+
+            Some(Comparison {
+                day: latest.day,
+                part: latest.part,
+                baseline: base.median,
+                latest: latest.median,
+                change,
+                regressed: change > threshold,
+            })
+        })
+        .collect();
+
+    Ok((baseline_run, latest_run, comparisons))
+}
+
+/// Run named by `name`: a run number if one matches exactly, otherwise the latest run of
+/// the only commit starting with `name`
+fn find_baseline<'a>(earlier: impl Iterator<Item = &'a Record>, name: &str) -> Result<u32> {
+    let earlier: Vec<&Record> = earlier.collect();
+    if let Some(record) = earlier.iter().find(|r| r.run.to_string() == name) {
+        return Ok(record.run);
+    }
+
+    let matches: Vec<&&Record> = earlier
+        .iter()
+        .filter(|r| r.commit.starts_with(name))
+        .collect();
+    let mut commits: Vec<&str> = matches.iter().map(|r| r.commit.as_str()).collect();
+    commits.sort_unstable();
+    commits.dedup();
+    if commits.len() > 1 {
+        bail!(
+            "Baseline '{}' is ambiguous, it matches commits {}",
+            name,
+            commits.join(", ")
+        );
+    }
+
+    matches
+        .iter()
+        .map(|r| r.run)
+        .max()
+        .with_context(|| format!("No earlier run matches baseline '{}'", name))
+}
+
+/// Print the comparison of the latest run, returning an error if anything regressed
+pub fn print_comparison(path: &Path, baseline: Option<&str>, threshold: f64) -> Result<()> {
+    let history = read_history(path)?;
+    let (baseline_run, latest_run, comparisons) = compare_runs(&history, baseline, threshold)?;
+
+    println!(
+        "Comparing run {} with run {} (threshold {:.0}%)",
+        latest_run,
+        baseline_run,
+        threshold * 100.0
+    );
+    for c in &comparisons {
+        println!(
+            "Day {:0>2} part {}: {:.1?} -> {:.1?} ({:+.1}%){}",
+            c.day,
+            c.part,
+            c.baseline,
+            c.latest,
+            c.change * 100.0,
+            if c.regressed { "  SLOWER" } else { "" }
+        );
+    }
+
+    let regressions = comparisons.iter().filter(|c| c.regressed).count();
+    if regressions > 0 {
+        bail!("{} part(s) got slower than the threshold", regressions);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(run: u32, commit: &str, day: u8, part: u8, median_us: u64) -> Record {
+        Record {
+            run,
+            timestamp: 1_700_000_000 + run as u64,
+            commit: commit.to_string(),
+            day,
+            part,
+            median: Duration::from_micros(median_us),
+        }
+    }
+
+    #[test]
+    fn test_history_round_trips_through_csv() {
+        let path =
+            std::env::temp_dir().join(format!("aoc-history-test-{}.csv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let records = vec![record(1, "abc123", 1, 1, 10), record(1, "abc123", 1, 2, 20)];
+
+        append_history(&path, &records[..1]).unwrap();
+        append_history(&path, &records[1..]).unwrap();
+
+        assert_eq!(read_history(&path).unwrap(), records);
+        assert!(fs::read_to_string(&path).unwrap().starts_with(HEADER));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_record_from_csv_rejects_bad_lines() {
+        assert!(Record::from_csv("1,2,abc,1,1").is_err());
+        assert!(Record::from_csv("1,2,abc,x,1,100").is_err());
+    }
+
+    #[test]
+    fn test_compare_runs_flags_regressions_against_previous_run() {
+        let history = vec![
+            record(1, "aaa", 1, 1, 100),
+            record(1, "aaa", 1, 2, 100),
+            record(2, "bbb", 1, 1, 105),
+            record(2, "bbb", 1, 2, 150),
+        ];
+
+        let (baseline, latest, comparisons) = compare_runs(&history, None, 0.1).unwrap();
+
+        assert_eq!((baseline, latest), (1, 2));
+        assert_eq!(
+            comparisons.iter().map(|c| c.regressed).collect::<Vec<_>>(),
+            vec![false, true]
+        );
+    }
+
+    #[test]
+    fn test_compare_runs_with_named_baseline() {
+        let history = vec![
+            record(1, "aaa", 1, 1, 100),
+            record(2, "bbb", 1, 1, 300),
+            record(3, "ccc", 1, 1, 150),
+        ];
+
+        let (baseline, _, comparisons) = compare_runs(&history, Some("aa"), 0.1).unwrap();
+        assert_eq!(baseline, 1);
+        assert!(comparisons[0].regressed);
+
+        let (baseline, _, comparisons) = compare_runs(&history, Some("2"), 0.1).unwrap();
+        assert_eq!(baseline, 2);
+        assert!(!comparisons[0].regressed);
+
+        assert!(compare_runs(&history, Some("zzz"), 0.1).is_err());
+    }
+
+    #[test]
+    fn test_compare_runs_prefers_run_numbers_and_rejects_ambiguous_commits() {
+        let history = vec![
+            record(1, "aaa111", 1, 1, 100),
+            record(2, "1bc222", 1, 1, 200),
+            record(3, "1bd333", 1, 1, 300),
+            record(4, "1bd333", 1, 1, 300),
+            record(5, "ccc444", 1, 1, 300),
+        ];
+
+        assert_eq!(compare_runs(&history, Some("1"), 0.1).unwrap().0, 1);
+        assert_eq!(compare_runs(&history, Some("1bc"), 0.1).unwrap().0, 2);
+        assert_eq!(compare_runs(&history, Some("1bd"), 0.1).unwrap().0, 4);
+        assert!(compare_runs(&history, Some("1b"), 0.1).is_err());
+    }
+
+    #[test]
+    fn test_compare_runs_needs_two_runs() {
+        assert!(compare_runs(&[], None, 0.1).is_err());
+        assert!(compare_runs(&[record(1, "aaa", 1, 1, 100)], None, 0.1).is_err());
+    }
+}
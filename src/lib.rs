@@ -1,22 +1,113 @@
 use anyhow::{Context, Result};
 use std::fs;
 
+pub mod alloc;
+pub mod bench;
+pub mod cache;
+pub mod check;
+pub mod days;
+pub mod fetch;
+pub mod ffi;
+pub mod history;
+pub mod readme;
+pub mod repl;
+pub mod runner;
+pub mod serve;
+pub mod solution;
+pub mod trace;
+pub mod watch;
+
+/// Inputs embedded at build time, empty without the `embed-inputs` feature
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+}
+
+/// Input of a given day embedded at build time with the `embed-inputs` feature
+pub fn embedded_input(day: u8) -> Option<&'static str> {
+    embedded::EMBEDDED
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, input)| *input)
+}
+
+/// Days whose inputs are embedded in the binary, in order
+pub fn embedded_days() -> Vec<u8> {
+    embedded::EMBEDDED.iter().map(|(day, _)| *day).collect()
+}
+
+/// Whether a given day has an input, either embedded or as a file
+pub fn has_input(day: u8) -> bool {
+    embedded_input(day).is_some() || fs::exists(input_path(day)).unwrap_or(false)
+}
+
+/// Path of the input file for a given day
+pub fn input_path(day: u8) -> String {
+    format!("input/day{:0>2}.txt", day)
+}
+
+/// Path of the example input file for a given day
+pub fn example_path(day: u8) -> String {
+    format!("input/day{:0>2}.example.txt", day)
+}
+
+/// Path of the file with the verified answers for a given day, part 1 on the first line
+/// and part 2 on the second
+pub fn answer_path(day: u8) -> String {
+    format!("input/day{:0>2}.answers.txt", day)
+}
+
+/// Read the verified answers for a given day, `None` for parts without one
+pub fn read_expected_answers(day: u8) -> [Option<String>; 2] {
+    let content = fs::read_to_string(answer_path(day)).unwrap_or_default();
+    let mut lines = content.lines().map(str::trim);
+
+    [(); 2].map(|_| {
+        lines
+            .next()
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+    })
+}
+
+/// Read input file for a given day as is, without any trimming.
+/// Embedded inputs take precedence over the file.
+pub fn read_input_raw(day: u8) -> Result<String> {
+    if let Some(input) = embedded_input(day) {
+        return Ok(input.to_string());
+    }
+    let filename = input_path(day);
+    fs::read_to_string(&filename).with_context(|| {
+        format!(
+            "Could not read file: {}, download it with `aoc fetch {}`",
+            filename, day
+        )
+    })
+}
+
 /// Read input file for a given day as a single string
 pub fn read_input(day: u8) -> Result<String> {
-    let filename = format!("input/day{:0>2}.txt", day);
-    let content = fs::read_to_string(&filename)
-        .with_context(|| format!("Could not read file: {}", filename))?;
-    Ok(content.trim().to_string())
+    Ok(read_input_raw(day)?.trim().to_string())
 }
 
 /// Read input file for a given day as lines
 pub fn read_input_lines(day: u8) -> Result<Vec<String>> {
-    let filename = format!("input/day{:0>2}.txt", day);
-    let content = fs::read_to_string(&filename)
-        .with_context(|| format!("Could not read file: {}", filename))?;
-    Ok(content
+    Ok(to_lines(&read_input_raw(day)?))
+}
+
+/// Split input into lines, skipping empty ones
+pub fn to_lines(input: &str) -> Vec<String> {
+    input
         .lines()
         .map(|s| s.to_string())
         .filter(|s| !s.is_empty())
-        .collect())
+        .collect()
+}
+
+/// Split input into a grid of characters, skipping empty lines
+pub fn to_grid(input: &str) -> Vec<Vec<char>> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
+        .collect()
 }
@@ -0,0 +1,244 @@
+use std::fs;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+
+use crate::bench::bench_part;
+use crate::days::{DAYS, Day};
+use crate::{read_expected_answers, read_input_raw};
+
+/// The generated table replaces everything between these two lines
+pub const START_MARKER: &str = "<!-- results:start -->";
+pub const END_MARKER: &str = "<!-- results:end -->";
+
+/// Status of a single part in the results table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    /// Answer matches the one in the answers file
+    Verified,
+    /// Answer differs from the one in the answers file
+    Wrong,
+    /// Solved, but there's no known answer to compare with
+    Unverified,
+    Failed,
+    NoInput,
+}
+
+impl PartStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            PartStatus::Verified => "verified",
+            PartStatus::Wrong => "wrong answer",
+            PartStatus::Unverified => "unverified",
+            PartStatus::Failed => "error",
+            PartStatus::NoInput => "no input",
+        }
+    }
+}
+
+/// A single day's row in the results table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultRow {
+    pub day: u8,
+    pub status: [PartStatus; 2],
+    pub median: [Option<Duration>; 2],
+    pub lines_of_code: usize,
+}
+
+/// Solve every day `samples` times and regenerate the results table in the README at `path`
+pub fn update_readme(path: &str, samples: usize) -> Result<()> {
+    let readme =
+        fs::read_to_string(path).with_context(|| format!("Could not read file: {}", path))?;
+    let rows: Vec<ResultRow> = DAYS.iter().map(|day| result_row(day, samples)).collect();
+
+    let updated = replace_section(&readme, &render_table(&rows))?;
+    fs::write(path, updated).with_context(|| format!("Could not write file: {}", path))?;
+    println!("Updated the results table in {}", path);
+
+    Ok(())
+}
+
+fn result_row(day: &Day, samples: usize) -> ResultRow {
+    let mut row = ResultRow {
+        day: day.day,
+        status: [PartStatus::NoInput; 2],
+        median: [None; 2],
+        lines_of_code: count_lines_of_code(day.source),
+    };
+    let Ok(input) = read_input_raw(day.day) else {
+        return row;
+    };
+    let expected = read_expected_answers(day.day);
+
+    for (idx, part) in [1, 2].into_iter().enumerate() {
+        match bench_part(day, &input, part, samples) {
+            Ok(bench) => {
+                row.status[idx] = match &expected[idx] {
+                    Some(answer) if *answer == bench.answer => PartStatus::Verified,
+                    Some(_) => PartStatus::Wrong,
+                    None => PartStatus::Unverified,
+                };
+                row.median[idx] = Some(bench.median());
+            }
+            Err(_) => row.status[idx] = PartStatus::Failed,
+        }
+    }
+
+    row
+}
+
+pub fn render_table(rows: &[ResultRow]) -> String {
+    let mut table = String::from(
+        "| Day | Part 1 | Part 2 | Part 1 median | Part 2 median | Lines of code |\n\
+         | --: | ------ | ------ | ------------: | ------------: | ------------: |\n",
+    );
+
+    for row in rows {
+        let median = |idx: usize| {
+            row.median[idx].map_or_else(|| "-".to_string(), |median| format!("{:.1?}", median))
+        };
+        table.push_str(&format!(
+            "| {:0>2} | {} | {} | {} | {} | {} |\n",
+            row.day,
+            row.status[0].label(),
+            row.status[1].label(),
+            median(0),
+            median(1),
+            row.lines_of_code
+        ));
+    }
+
+    table
+}
+
+/// Replace the lines between the start and end markers with `table`, keeping the markers
+pub fn replace_section(readme: &str, table: &str) -> Result<String> {
+    let Some(start) = readme.find(START_MARKER) else {
+        bail!("README should contain the marker {}", START_MARKER);
+    };
+    let content_start = start + START_MARKER.len();
+    let Some(end) = readme[content_start..].find(END_MARKER) else {
+        bail!(
+            "README should contain the marker {} after {}",
+            END_MARKER,
+            START_MARKER
+        );
+    };
+    let content_end = content_start + end;
+
+    Ok(format!(
+        "{}\n\n{}\n\n{}",
+        &readme[..content_start],
+        table.trim_end(),
+        &readme[content_end..]
+    ))
+}
+
+/// Count the non-empty, non-comment lines of a day's source, leaving out its tests module
+pub fn count_lines_of_code(source: &str) -> usize {
+    let lines: Vec<&str> = source.lines().map(str::trim).collect();
+    let mut end = lines
+        .iter()
+        .position(|line| line.starts_with("mod tests"))
+        .unwrap_or(lines.len());
+    if end > 0 && lines[end - 1] == "#[cfg(test)]" {
+        end -= 1;
+    }
+
+    lines[..end]
+        .iter()
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_lines_of_code_skips_comments_blanks_and_tests() {
+        let source = "use x;\n\n/// Doc\nfn a() {\n    // comment\n    b();\n}\n\n#[cfg(test)]\nmod tests {}\n";
+
+        assert_eq!(count_lines_of_code(source), 4);
+    }
+
+    #[test]
+    fn test_count_lines_of_code_counts_test_only_code_outside_tests() {
+        let source = "fn a() {}
+
+#[cfg(test)]
+fn helper() {}
+
+fn b() {}
+
+#[cfg(test)]
+mod tests {
+    fn c() {}
+}
+";
+
+        assert_eq!(count_lines_of_code(source), 4);
+    }
+
+    #[test]
+    fn test_render_table() {
+        let rows = vec![
+            ResultRow {
+                day: 1,
+                status: [PartStatus::Verified, PartStatus::Unverified],
+                median: [
+                    Some(Duration::from_micros(12)),
+                    Some(Duration::from_millis(3)),
+                ],
+                lines_of_code: 42,
+            },
+            ResultRow {
+                day: 2,
+                status: [PartStatus::NoInput; 2],
+                median: [None; 2],
+                lines_of_code: 7,
+            },
+        ];
+
+        let table = render_table(&rows);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "| 01 | verified | unverified | 12.0µs | 3.0ms | 42 |"
+        );
+        assert_eq!(lines[3], "| 02 | no input | no input | - | - | 7 |");
+    }
+
+    #[test]
+    fn test_replace_section_only_replaces_between_markers() {
+        let readme = format!(
+            "# Title\n\n{}\nold table\n{}\n\nFooter\n",
+            START_MARKER, END_MARKER
+        );
+
+        assert_eq!(
+            replace_section(&readme, "| new |\n").unwrap(),
+            format!(
+                "# Title\n\n{}\n\n| new |\n\n{}\n\nFooter\n",
+                START_MARKER, END_MARKER
+            )
+        );
+    }
+
+    #[test]
+    fn test_replace_section_is_idempotent() {
+        let readme = format!("{}\n{}\n", START_MARKER, END_MARKER);
+        let once = replace_section(&readme, "| new |\n").unwrap();
+
+        assert_eq!(replace_section(&once, "| new |\n").unwrap(), once);
+    }
+
+    #[test]
+    fn test_replace_section_requires_markers() {
+        assert!(replace_section("# Title\n", "| new |\n").is_err());
+        assert!(replace_section(START_MARKER, "| new |\n").is_err());
+        assert!(replace_section(&format!("{}\n{}", END_MARKER, START_MARKER), "").is_err());
+    }
+}
@@ -0,0 +1,401 @@
+//! Interactive session for experimenting with a single day: load or paste an input,
+//! change the solver's parameters and solve again without recompiling.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::days::{Day, find_day};
+use crate::runner::{PartReport, format_allocs, solve_part};
+use crate::solution::{Params, Solution, parse_value};
+use crate::trace::{self, Level};
+use crate::{embedded_input, example_path, has_input, input_path, read_input_raw};
+
+const HELP: &str = "Commands:
+  day N            Switch to day N, loading its input file if there is one
+  load [PATH]      Load the day's input file, the example file with `example`, or PATH
+  paste            Read an input from the following lines, up to a line containing only `end`
+  run [PART]       Solve both parts, or only PART, of the loaded input
+  params           List the day's parameters and their current values
+  set NAME VALUE   Change a parameter
+  reset            Restore the parameters' defaults
+  show [LINES]     Print the parsed input, up to LINES lines (40 by default)
+  inspect [LINES]  Print the solver's intermediate state, such as day 01's dial after each
+                   command or day 04's neighbour counts, up to LINES lines (40 by default)
+  check            Validate the loaded input against the day's grammar
+  trace LEVEL      Trace solver state to stderr, 0 (off) to 3 (everything)
+  help             Print this help
+  quit             Leave the REPL";
+
+/// Number of lines `show` prints unless told otherwise
+const DEFAULT_SHOW_LINES: usize = 40;
+
+/// A day's parsed input kept between commands, with the day's solution type erased
+pub trait Loaded {
+    /// Solve and time a single part of the parsed input
+    fn solve(&self, part: u8, params: &Params) -> PartReport;
+
+    /// Pretty-printed parsed input
+    fn dump(&self) -> String;
+
+    /// Up to `limit` lines of the solver's intermediate state
+    fn inspect(&self, params: &Params, limit: usize) -> Result<Vec<String>>;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> Loaded for Parsed<S> {
+    fn solve(&self, part: u8, params: &Params) -> PartReport {
+        solve_part::<S>(&self.0, part, params)
+    }
+
+    fn dump(&self) -> String {
+        format!("{:#?}", self.0)
+    }
+
+    fn inspect(&self, params: &Params, limit: usize) -> Result<Vec<String>> {
+        S::inspect(&self.0, params, limit)
+    }
+}
+
+/// Parse `input` with the day's solution, keeping the result for later commands
+pub fn load<S: Solution + 'static>(input: &str) -> Result<Box<dyn Loaded>> {
+    Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+}
+
+/// State of a REPL session
+pub struct Repl {
+    day: &'static Day,
+    /// Raw and parsed form of the loaded input
+    input: Option<(String, Box<dyn Loaded>)>,
+    params: Params,
+}
+
+impl Repl {
+    pub fn new(day: u8) -> Result<Self> {
+        Ok(Repl {
+            day: find_day(day).with_context(|| format!("Day {} is not solved yet", day))?,
+            input: None,
+            params: Params::default(),
+        })
+    }
+
+    /// Read commands from `lines` until they run out or `quit` is given,
+    /// writing a prompt before each command
+    pub fn run(
+        &mut self,
+        lines: &mut impl Iterator<Item = String>,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        // A broken input shouldn't end the session, another one can still be loaded
+        if let Err(err) = self.load_default(out) {
+            writeln!(out, "error: {:#}", err)?;
+        }
+
+        loop {
+            write!(out, "day{:0>2}> ", self.day.day)?;
+            out.flush()?;
+            let Some(line) = lines.next() else {
+                writeln!(out)?;
+                return Ok(());
+            };
+
+            match self.execute(&line, lines, out) {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(err) => writeln!(out, "error: {:#}", err)?,
+            }
+        }
+    }
+
+    /// Execute a single command, reading pasted input from `lines`.
+    /// Returns `false` when the session should end.
+    pub fn execute(
+        &mut self,
+        line: &str,
+        lines: &mut impl Iterator<Item = String>,
+        out: &mut impl Write,
+    ) -> Result<bool> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words[..] {
+            [] => {}
+            ["quit" | "exit"] => return Ok(false),
+            ["help"] => writeln!(out, "{}", HELP)?,
+            ["day", day] => {
+                let day = day
+                    .parse::<u8>()
+                    .with_context(|| format!("Should be able to parse day '{}'", day))?;
+                *self = Repl::new(day)?;
+                self.load_default(out)?;
+            }
+            ["load"] => self.load_day_input(out)?,
+            ["load", "example"] => self.load_file(&example_path(self.day.day), out)?,
+            ["load", path] => self.load_file(path, out)?,
+            ["paste"] => {
+                let input: String = lines
+                    .take_while(|line| line != "end")
+                    .map(|line| line + "\n")
+                    .collect();
+                self.load_input(input, "pasted input", out)?;
+            }
+            ["run"] => self.solve(&[1, 2], out)?,
+            ["run", "1"] => self.solve(&[1], out)?,
+            ["run", "2"] => self.solve(&[2], out)?,
+            ["params"] => {
+                if self.day.params.is_empty() {
+                    writeln!(out, "Day {:0>2} has no parameters", self.day.day)?;
+                }
+                for param in self.day.params {
+                    writeln!(
+                        out,
+                        "{} = {} (default {}): {}",
+                        param.name,
+                        self.params.get(param),
+                        param.default,
+                        param.description
+                    )?;
+                    for (item, value) in self.params.items(param) {
+                        writeln!(out, "{}.{} = {}", param.name, item, value)?;
+                    }
+                }
+            }
+            ["set", name, value] => {
+                let value = parse_value(value)
+                    .with_context(|| format!("Should be able to parse value '{}'", value))?;
+                self.params.set(self.day.params, name, value)?;
+            }
+            ["reset"] => self.params = Params::default(),
+            ["show"] => self.show(DEFAULT_SHOW_LINES, out)?,
+            ["show", limit] => {
+                let limit = limit
+                    .parse::<usize>()
+                    .with_context(|| format!("Should be able to parse line count '{}'", limit))?;
+                self.show(limit, out)?;
+            }
+            ["inspect"] => self.inspect(DEFAULT_SHOW_LINES, out)?,
+            ["inspect", limit] => {
+                let limit = limit
+                    .parse::<usize>()
+                    .with_context(|| format!("Should be able to parse line count '{}'", limit))?;
+                self.inspect(limit, out)?;
+            }
+            ["check"] => {
+                let (raw, _) = self.loaded()?;
+                let problems = (self.day.check)(raw, &self.params);
+                if problems.is_empty() {
+                    writeln!(out, "ok")?;
+                }
+                for problem in problems {
+                    writeln!(out, "{}", problem)?;
+                }
+            }
+            ["trace", level] => match level.parse::<u8>() {
+                Ok(verbosity @ 0..=3) => trace::set_level(Level::from_verbosity(verbosity)),
+                _ => bail!("Trace level should be 0 to 3, got '{}'", level),
+            },
+            _ => bail!("Unknown command '{}', try `help`", line.trim()),
+        }
+
+        Ok(true)
+    }
+
+    /// Load the day's input if it has one
+    fn load_default(&mut self, out: &mut impl Write) -> Result<()> {
+        if has_input(self.day.day) {
+            self.load_day_input(out)
+        } else {
+            writeln!(
+                out,
+                "Day {:0>2}: no input file, use `load PATH` or `paste`",
+                self.day.day
+            )?;
+            Ok(())
+        }
+    }
+
+    /// Load the day's embedded input or input file
+    fn load_day_input(&mut self, out: &mut impl Write) -> Result<()> {
+        let day = self.day.day;
+        let source = if embedded_input(day).is_some() {
+            "embedded input".to_string()
+        } else {
+            input_path(day)
+        };
+        self.load_input(read_input_raw(day)?, &source, out)
+    }
+
+    fn load_file(&mut self, path: &str, out: &mut impl Write) -> Result<()> {
+        let input =
+            fs::read_to_string(path).with_context(|| format!("Could not read file: {}", path))?;
+        self.load_input(input, path, out)
+    }
+
+    fn load_input(&mut self, input: String, source: &str, out: &mut impl Write) -> Result<()> {
+        let start = Instant::now();
+        let parsed = (self.day.load)(&input)?;
+        writeln!(
+            out,
+            "Day {:0>2}: loaded {} ({} lines, parsed in {:?})",
+            self.day.day,
+            source,
+            input.lines().count(),
+            start.elapsed()
+        )?;
+        self.input = Some((input, parsed));
+
+        Ok(())
+    }
+
+    fn loaded(&self) -> Result<(&str, &dyn Loaded)> {
+        self.input
+            .as_ref()
+            .map(|(raw, parsed)| (raw.as_str(), parsed.as_ref()))
+            .ok_or_else(|| anyhow!("No input loaded, use `load` or `paste` first"))
+    }
+
+    fn solve(&self, parts: &[u8], out: &mut impl Write) -> Result<()> {
+        let (_, parsed) = self.loaded()?;
+
+        for &part in parts {
+            let report = parsed.solve(part, &self.params);
+            match report.answer {
+                Ok(answer) => writeln!(
+                    out,
+                    "Part {}: {} ({:?}{})",
+                    part,
+                    answer,
+                    report.elapsed,
+                    format_allocs(report.allocs)
+                )?,
+                Err(err) => writeln!(out, "Part {}: error: {:#}", part, err)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn show(&self, limit: usize, out: &mut impl Write) -> Result<()> {
+        let (_, parsed) = self.loaded()?;
+        let dump = parsed.dump();
+        let total = dump.lines().count();
+
+        for line in dump.lines().take(limit) {
+            writeln!(out, "{}", line)?;
+        }
+        if total > limit {
+            writeln!(out, "... {} more lines", total - limit)?;
+        }
+
+        Ok(())
+    }
+
+    fn inspect(&self, limit: usize, out: &mut impl Write) -> Result<()> {
+        let (_, parsed) = self.loaded()?;
+        // One line more than shown tells whether there is more to see
+        let lines = parsed.inspect(&self.params, limit.saturating_add(1))?;
+
+        for line in lines.iter().take(limit) {
+            writeln!(out, "{}", line)?;
+        }
+        if lines.len() > limit {
+            writeln!(out, "... more lines")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run a REPL on stdin and stdout, starting with the given day
+pub fn repl(day: u8) -> Result<()> {
+    let mut repl = Repl::new(day)?;
+    let mut lines = io::stdin().lock().lines().map_while(Result::ok);
+
+    repl.run(&mut lines, &mut io::stdout())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Execute `commands` in a fresh day 3 session, returning the output
+    fn session(commands: &str) -> String {
+        let mut repl = Repl::new(3).unwrap();
+        let mut lines = commands.lines().map(str::to_string);
+        let mut out = vec![];
+
+        while let Some(line) = lines.next() {
+            if let Err(err) = repl.execute(&line, &mut lines, &mut out) {
+                writeln!(out, "error: {:#}", err).unwrap();
+            }
+        }
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_paste_and_run_with_params() {
+        let output = session(
+            "paste\n987654321111111\n811111111111119\nend\nrun 1\nset part1_digits 3\nrun 1",
+        );
+        let answers: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix("Part 1: "))
+            .map(|line| line.split_whitespace().next().unwrap())
+            .collect();
+
+        assert!(output.starts_with("Day 03: loaded pasted input (2 lines"));
+        assert_eq!(answers, vec!["187", "1806"]);
+    }
+
+    #[test]
+    fn test_show_truncates_parsed_input() {
+        let output = session("paste\n987654321111111\n811111111111119\nend\nshow 2");
+
+        assert!(output.contains("\"987654321111111\",\n"));
+        assert!(output.ends_with("... 2 more lines\n"));
+    }
+
+    #[test]
+    fn test_inspect_uses_the_days_hook_and_params() {
+        let mut repl = Repl::new(4).unwrap();
+        let mut lines = "@@.\n@.@\nend".lines().map(str::to_string);
+        let mut out = vec![];
+        repl.execute("paste", &mut lines, &mut out).unwrap();
+        repl.execute("inspect 1", &mut lines, &mut out).unwrap();
+        repl.execute("day 1", &mut lines, &mut out).unwrap();
+        let mut lines = "R50 L5\nend".lines().map(str::to_string);
+        repl.execute("paste", &mut lines, &mut out).unwrap();
+        repl.execute("set dial_target 95", &mut lines, &mut out)
+            .unwrap();
+        repl.execute("inspect", &mut lines, &mut out).unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("\n23.\n... more lines\n"));
+        assert!(output.ends_with(
+            "R50: dial at 0, 0 landings, 1 passes\nL5: dial at 95, 1 landings, 2 passes\n"
+        ));
+        assert!(session("paste\n123\nend\ninspect").contains("error: Nothing to inspect"));
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        let output = session("run\nset unknown 1\nfoo");
+        let errors: Vec<&str> = output.lines().filter(|l| l.starts_with("error")).collect();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("No input loaded"));
+        assert!(errors[1].contains("Unknown parameter"));
+        assert!(errors[2].contains("Unknown command"));
+    }
+
+    #[test]
+    fn test_quit_ends_session() {
+        let mut repl = Repl::new(1).unwrap();
+        let mut lines = std::iter::empty();
+
+        assert!(!repl.execute("quit", &mut lines, &mut vec![]).unwrap());
+    }
+}
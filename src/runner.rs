@@ -0,0 +1,1201 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::alloc::{AllocStats, format_bytes, measure};
+use crate::cache::{Cache, CacheKey, DEFAULT_CACHE_DIR, hash_input};
+use crate::days::{DAYS, Day, find_day};
+use crate::fetch::{DEFAULT_INTERVAL, DEFAULT_SESSION_FILE};
+use crate::history::DEFAULT_HISTORY_PATH;
+use crate::serve::{DEFAULT_ADDR, Limits};
+use crate::solution::{Params, Solution, find_param, parse_value};
+use crate::trace::{self, Level};
+use crate::{embedded_days, has_input, read_input_raw};
+
+const USAGE: &str = "Usage: aoc [OPTIONS] [DAY...]
+       aoc watch DAY
+       aoc repl [DAY]
+       aoc embedded
+       aoc export DAY NAME [--input PATH] [--param NAME=VALUE]...
+       aoc serve [--addr ADDR] [--max-body BYTES] [--timeout SECONDS]
+                 [--max-connections N] [--max-solvers N]
+       aoc fetch [--base-url URL] [--session-file PATH] [--interval SECONDS] [--force] [DAY...]
+       aoc readme [--samples N] [PATH]
+       aoc bench [--samples N] [--history PATH] [DAY...]
+       aoc bench compare [--baseline RUN|COMMIT] [--threshold PERCENT] [--history PATH]
+
+Runs the given days, or every day with an input file when no days are given.
+`watch` solves a single day again whenever its input or example file changes.
+`repl` starts an interactive session for loading inputs, changing solver parameters
+and solving again, starting with DAY or day 1.
+`embedded` lists the days whose inputs are built into the binary (`embed-inputs` feature).
+`export` writes an extra output of a day's solver to stdout, e.g. `zero-events` or `lock` for day 1
+or `repeats` for day 2, computed from the day's input or the file at PATH. Without NAME it lists the day's exports.
+`serve` answers `POST /day/<n>/part/<p>` requests carrying the puzzle input as the body
+with JSON, listening on 127.0.0.1:8080 by default. At most 64 connections and 8 solvers
+are handled at once by default, further requests being answered with 503.
+`fetch` downloads the missing inputs of the given days, or of every day, using the session
+token in AOC_SESSION or .aoc-session. AOC_BASE_URL also sets the base URL.
+`readme` regenerates the results table in README.md, or the file at PATH.
+`bench` times each part and appends the medians to bench_history.csv as a new run.
+`bench compare` flags parts of the latest run that got slower than the threshold
+(10% by default) compared to the run before it or the given baseline run.
+
+Options:
+  --part PART        Only solve part 1 or 2
+  --jobs N           Number of worker threads, defaults to the number of CPUs
+  --param NAME=VALUE Override a solver parameter, e.g. dial_target=5 for day 1.
+                     Answers solved with overridden parameters aren't cached
+  -v, -vv, -vvv      Trace intermediate solver state to stderr, solving even cached days
+  --check            Validate the inputs against each day's grammar without solving
+  --no-cache         Neither read nor write the answer cache
+  --refresh-cache    Solve again and overwrite the cached answers
+  --clear-cache      Remove every cached answer and exit";
+
+/// How the answer cache is used when solving
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Use cached answers when available and store new ones
+    #[default]
+    Use,
+    /// Neither read nor write the cache
+    Bypass,
+    /// Ignore cached answers but store the new ones
+    Refresh,
+}
+
+/// Options parsed from the command line
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Days to run, empty meaning every day
+    pub days: Vec<u8>,
+    /// Part to solve, `None` meaning both
+    pub part: Option<u8>,
+    /// Number of worker threads, `None` meaning one per CPU
+    pub jobs: Option<usize>,
+    /// Solver parameters to override, applied to the days that have them
+    pub params: Vec<(String, usize)>,
+    /// Only validate the inputs instead of solving
+    pub check: bool,
+    /// Number of `-v` flags, selecting the trace level
+    pub verbosity: u8,
+    pub cache: CacheMode,
+    /// Remove every cached answer instead of running anything
+    pub clear_cache: bool,
+}
+
+impl Options {
+    /// Parts selected to be solved
+    pub fn parts(&self) -> &'static [u8] {
+        match self.part {
+            Some(1) => &[1],
+            Some(2) => &[2],
+            _ => &[1, 2],
+        }
+    }
+
+    /// Number of worker threads to use
+    pub fn jobs(&self) -> usize {
+        self.jobs
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+
+    /// Whether the parts of each of `days` days solved at once can get their own threads
+    /// without the solvers using more threads than `jobs` in total
+    pub fn parallel_parts(&self, days: usize) -> bool {
+        let jobs = self.jobs();
+        let parts = self.parts().len();
+
+        parts > 1 && jobs.min(days.max(1)) * parts <= jobs
+    }
+}
+
+/// What the runner was asked to do
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Solve or check the selected days once
+    Run(Options),
+    /// Solve a single day again whenever its input files change
+    Watch(u8),
+    /// Start an interactive session with the given day
+    Repl(u8),
+    /// List the days with inputs embedded in the binary
+    Embedded,
+    /// Write an extra output of a day's solver, or list them if `name` is `None`
+    Export {
+        day: u8,
+        name: Option<String>,
+        /// `None` to read the day's input
+        input: Option<String>,
+        params: Vec<(String, usize)>,
+    },
+    /// Answer HTTP requests on `addr`
+    Serve { addr: String, limits: Limits },
+    /// Download the missing inputs of `days`, every day if empty
+    Fetch {
+        days: Vec<u8>,
+        /// `None` to use `AOC_BASE_URL` or the default
+        base_url: Option<String>,
+        session_file: String,
+        interval: Duration,
+        /// Download again even if the input exists
+        force: bool,
+    },
+    /// Regenerate the results table of the README at `path`, timing each part `samples` times
+    Readme { path: String, samples: usize },
+    /// Time both parts of the given days, all if empty, and append the medians to the history
+    Bench {
+        days: Vec<u8>,
+        samples: usize,
+        history: String,
+    },
+    /// Compare the latest benchmark run with the previous one or with `baseline`
+    Compare {
+        history: String,
+        baseline: Option<String>,
+        /// Relative slowdown that counts as a regression, e.g. `0.1` for 10%
+        threshold: f64,
+    },
+}
+
+/// Default number of times each part is solved when timing it
+pub const DEFAULT_SAMPLES: usize = 11;
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command> {
+    let mut args = args.into_iter().peekable();
+
+    if args.peek().map(String::as_str) == Some("watch") {
+        args.next();
+        let (Some(day), None) = (args.next(), args.next()) else {
+            bail!("watch takes exactly one day\n\n{}", USAGE);
+        };
+        return Ok(Command::Watch(parse_day(&day)?));
+    }
+
+    if args.peek().map(String::as_str) == Some("repl") {
+        args.next();
+        return match (args.next(), args.next()) {
+            (None, _) => Ok(Command::Repl(1)),
+            (Some(day), None) => Ok(Command::Repl(parse_day(&day)?)),
+            _ => bail!("repl takes at most one day\n\n{}", USAGE),
+        };
+    }
+
+    if args.peek().map(String::as_str) == Some("embedded") {
+        args.next();
+        if let Some(arg) = args.next() {
+            bail!("Unexpected argument '{}'\n\n{}", arg, USAGE);
+        }
+        return Ok(Command::Embedded);
+    }
+
+    if args.peek().map(String::as_str) == Some("export") {
+        args.next();
+        let day = parse_day(&args.next().context("export needs a day")?)?;
+        let mut name = None;
+        let mut input = None;
+        let mut params = vec![];
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => input = Some(args.next().context("--input needs a path")?),
+                "--param" => params.push(parse_param(&args.next().unwrap_or_default())?),
+                _ if arg.starts_with('-') => bail!("Unknown option '{}'\n\n{}", arg, USAGE),
+                _ if name.is_none() => name = Some(arg),
+                _ => bail!("Unexpected argument '{}'\n\n{}", arg, USAGE),
+            }
+        }
+        return Ok(Command::Export {
+            day,
+            name,
+            input,
+            params,
+        });
+    }
+
+    if args.peek().map(String::as_str) == Some("serve") {
+        args.next();
+        let mut addr = DEFAULT_ADDR.to_string();
+        let mut limits = Limits::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--addr" => addr = args.next().context("--addr needs an address")?,
+                "--max-body" => {
+                    let bytes = args.next().unwrap_or_default();
+                    limits.max_body = bytes.parse::<usize>().with_context(|| {
+                        format!("--max-body should be a number of bytes, got '{}'", bytes)
+                    })?;
+                }
+                "--timeout" => {
+                    let seconds = args.next().unwrap_or_default();
+                    limits.timeout = match seconds.parse::<f64>() {
+                        Ok(seconds) if seconds > 0.0 => Duration::from_secs_f64(seconds),
+                        _ => bail!(
+                            "--timeout should be a positive number of seconds, got '{}'",
+                            seconds
+                        ),
+                    };
+                }
+                "--max-connections" => {
+                    limits.max_connections =
+                        parse_positive("--max-connections", args.next().unwrap_or_default())?
+                }
+                "--max-solvers" => {
+                    limits.max_solvers =
+                        parse_positive("--max-solvers", args.next().unwrap_or_default())?
+                }
+                _ => bail!("Unknown argument '{}'\n\n{}", arg, USAGE),
+            }
+        }
+        return Ok(Command::Serve { addr, limits });
+    }
+
+    if args.peek().map(String::as_str) == Some("fetch") {
+        args.next();
+        let mut days = vec![];
+        let mut base_url = None;
+        let mut session_file = DEFAULT_SESSION_FILE.to_string();
+        let mut interval = DEFAULT_INTERVAL;
+        let mut force = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--base-url" => base_url = Some(args.next().context("--base-url needs a URL")?),
+                "--session-file" => {
+                    session_file = args.next().context("--session-file needs a path")?
+                }
+                "--interval" => {
+                    let seconds = args.next().unwrap_or_default();
+                    interval = match seconds.parse::<f64>() {
+                        Ok(seconds) if seconds >= 0.0 => Duration::from_secs_f64(seconds),
+                        _ => bail!(
+                            "--interval should be a number of seconds, got '{}'",
+                            seconds
+                        ),
+                    };
+                }
+                "--force" => force = true,
+                _ if arg.starts_with('-') => bail!("Unknown option '{}'\n\n{}", arg, USAGE),
+                _ => days.push(parse_day(&arg)?),
+            }
+        }
+        return Ok(Command::Fetch {
+            days,
+            base_url,
+            session_file,
+            interval,
+            force,
+        });
+    }
+
+    if args.peek().map(String::as_str) == Some("readme") {
+        args.next();
+        let mut path = "README.md".to_string();
+        let mut samples = DEFAULT_SAMPLES;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--samples" => samples = parse_samples(args.next())?,
+                _ if arg.starts_with('-') => bail!("Unknown option '{}'\n\n{}", arg, USAGE),
+                _ => path = arg,
+            }
+        }
+        return Ok(Command::Readme { path, samples });
+    }
+
+    if args.peek().map(String::as_str) == Some("bench") {
+        args.next();
+        return parse_bench_args(args);
+    }
+
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args.next().unwrap_or_default();
+                options.part = match part.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => bail!("--part should be 1 or 2, got '{}'", part),
+                };
+            }
+            "--jobs" => {
+                let jobs = args.next().unwrap_or_default();
+                options.jobs = match jobs.parse::<usize>() {
+                    Ok(jobs) if jobs > 0 => Some(jobs),
+                    _ => bail!("--jobs should be a positive number, got '{}'", jobs),
+                };
+            }
+            "--param" => options
+                .params
+                .push(parse_param(&args.next().unwrap_or_default())?),
+            "--check" => options.check = true,
+            _ if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v') => {
+                options.verbosity = options.verbosity.saturating_add(arg.len() as u8 - 1);
+            }
+            "--no-cache" => options.cache = CacheMode::Bypass,
+            "--refresh-cache" => options.cache = CacheMode::Refresh,
+            "--clear-cache" => options.clear_cache = true,
+            "-h" | "--help" => bail!("{}", USAGE),
+            _ if arg.starts_with('-') => bail!("Unknown option '{}'\n\n{}", arg, USAGE),
+            _ => options.days.push(parse_day(&arg)?),
+        }
+    }
+
+    Ok(Command::Run(options))
+}
+
+fn parse_bench_args(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut history = DEFAULT_HISTORY_PATH.to_string();
+    let mut args = args.by_ref().peekable();
+
+    if args.peek().map(String::as_str) == Some("compare") {
+        args.next();
+        let mut baseline = None;
+        let mut threshold = 0.1;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--history" => history = args.next().context("--history needs a path")?,
+                "--baseline" => baseline = Some(args.next().context("--baseline needs a run")?),
+                "--threshold" => {
+                    let percent = args.next().unwrap_or_default();
+                    threshold = match percent.parse::<f64>() {
+                        Ok(percent) if percent >= 0.0 => percent / 100.0,
+                        _ => bail!("--threshold should be a percentage, got '{}'", percent),
+                    };
+                }
+                _ => bail!("Unknown argument '{}'\n\n{}", arg, USAGE),
+            }
+        }
+        return Ok(Command::Compare {
+            history,
+            baseline,
+            threshold,
+        });
+    }
+
+    let mut days = vec![];
+    let mut samples = DEFAULT_SAMPLES;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--history" => history = args.next().context("--history needs a path")?,
+            "--samples" => samples = parse_samples(args.next())?,
+            _ if arg.starts_with('-') => bail!("Unknown option '{}'\n\n{}", arg, USAGE),
+            _ => days.push(parse_day(&arg)?),
+        }
+    }
+
+    Ok(Command::Bench {
+        days,
+        samples,
+        history,
+    })
+}
+
+fn parse_samples(arg: Option<String>) -> Result<usize> {
+    let arg = arg.unwrap_or_default();
+    match arg.parse::<usize>() {
+        Ok(samples) if samples > 0 => Ok(samples),
+        _ => bail!("--samples should be a positive number, got '{}'", arg),
+    }
+}
+
+fn parse_positive(option: &str, arg: String) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => bail!("{} should be a positive number, got '{}'", option, arg),
+    }
+}
+
+fn parse_param(arg: &str) -> Result<(String, usize)> {
+    let parsed = arg
+        .split_once('=')
+        .and_then(|(name, value)| Some((name.to_string(), parse_value(value)?)));
+
+    parsed.with_context(|| {
+        format!(
+            "--param should be NAME=VALUE with a number, true or false, got '{}'",
+            arg
+        )
+    })
+}
+
+fn parse_day(arg: &str) -> Result<u8> {
+    let day = arg
+        .parse::<u8>()
+        .with_context(|| format!("Should be able to parse day '{}'", arg))?;
+    if find_day(day).is_none() {
+        bail!("Day {} is not available", day);
+    }
+
+    Ok(day)
+}
+
+/// Answer and timing of a single part
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<String>,
+    pub elapsed: Duration,
+    /// Heap usage while solving, `None` unless built with the `alloc-count` feature
+    pub allocs: Option<AllocStats>,
+}
+
+/// Timings and answers of a single day
+#[derive(Debug)]
+pub struct DayReport {
+    pub parse_elapsed: Duration,
+    /// Reports of the solved parts in order
+    pub parts: Vec<PartReport>,
+}
+
+/// Parse the input and solve the given parts, timing each step separately.
+/// With `parallel` the parts share the parsed input and are solved on their own threads.
+pub fn solve<S: Solution>(
+    input: &str,
+    parts: &[u8],
+    parallel: bool,
+    params: &Params,
+) -> Result<DayReport> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_elapsed = start.elapsed();
+
+    let parts = if parallel && parts.len() > 1 {
+        thread::scope(|scope| {
+            let handles: Vec<_> = parts
+                .iter()
+                .map(|&part| {
+                    let parsed = &parsed;
+                    scope.spawn(move || solve_part::<S>(parsed, part, params))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .zip(parts)
+                .map(|(handle, &part)| {
+                    handle.join().unwrap_or_else(|_| PartReport {
+                        part,
+                        answer: Err(anyhow!("Part {} panicked", part)),
+                        elapsed: Duration::ZERO,
+                        allocs: None,
+                    })
+                })
+                .collect()
+        })
+    } else {
+        parts
+            .iter()
+            .map(|&part| solve_part::<S>(&parsed, part, params))
+            .collect()
+    };
+
+    Ok(DayReport {
+        parse_elapsed,
+        parts,
+    })
+}
+
+/// Parse the input and produce the export called `name`
+pub fn export<S: Solution>(input: &str, name: &str, params: &Params) -> Result<String> {
+    if !S::EXPORTS.contains(&name) {
+        bail!(
+            "Unknown export '{}', available: {}",
+            name,
+            S::EXPORTS.join(", ")
+        );
+    }
+
+    S::export(&S::parse(input)?, name, params)
+}
+
+/// Write a day's export to stdout, or list its exports without a name
+pub fn print_export(
+    day: u8,
+    name: Option<&str>,
+    input: Option<&str>,
+    overrides: &[(String, usize)],
+) -> Result<()> {
+    let day = find_day(day).with_context(|| format!("Day {} is not solved yet", day))?;
+    let Some(name) = name else {
+        if day.exports.is_empty() {
+            println!("Day {:0>2} has no exports", day.day);
+        }
+        for name in day.exports {
+            println!("{}", name);
+        }
+        return Ok(());
+    };
+
+    let mut params = Params::default();
+    for (param, value) in overrides {
+        params.set(day.params, param, *value)?;
+    }
+    let input = match input {
+        Some(path) => {
+            fs::read_to_string(path).with_context(|| format!("Could not read file: {}", path))?
+        }
+        None => read_input_raw(day.day)?,
+    };
+    print!("{}", (day.export)(&input, name, &params)?);
+
+    Ok(())
+}
+
+/// Solve and time a single part of an already parsed input
+pub(crate) fn solve_part<S: Solution>(parsed: &S::Input, part: u8, params: &Params) -> PartReport {
+    let ((answer, elapsed), allocs) = measure(|| {
+        let start = Instant::now();
+        let answer = trace::in_part(part, || match part {
+            1 => S::part1_with(parsed, params),
+            2 => S::part2_with(parsed, params),
+            _ => Err(anyhow!("Part {} does not exist", part)),
+        });
+        (answer, start.elapsed())
+    });
+
+    PartReport {
+        part,
+        answer,
+        elapsed,
+        allocs,
+    }
+}
+
+/// Print the days whose inputs are embedded in the binary
+pub fn list_embedded() {
+    let days = embedded_days();
+
+    if !cfg!(feature = "embed-inputs") {
+        println!("No inputs are embedded, build with `--features embed-inputs` to embed them");
+    } else if days.is_empty() {
+        println!("No inputs are embedded, none existed in input/ at build time");
+    } else {
+        let days: Vec<String> = days.iter().map(|day| format!("{:0>2}", day)).collect();
+        println!("Embedded inputs: days {}", days.join(", "));
+    }
+}
+
+/// Run the days selected in `options`, returning an error if any of them failed.
+/// Days are run concurrently, but their results are printed in day order.
+pub fn run(options: &Options) -> Result<()> {
+    trace::set_level(Level::from_verbosity(options.verbosity));
+    let cache = Cache::new(DEFAULT_CACHE_DIR);
+    if options.clear_cache {
+        cache.clear()?;
+        println!("Cleared the answer cache in {}", DEFAULT_CACHE_DIR);
+        return Ok(());
+    }
+
+    let explicit = !options.days.is_empty();
+    let days: Vec<&Day> = if explicit {
+        options
+            .days
+            .iter()
+            .filter_map(|&day| find_day(day))
+            .collect()
+    } else {
+        DAYS.iter().collect()
+    };
+
+    for (name, value) in &options.params {
+        let with_param: Vec<&Day> = days
+            .iter()
+            .copied()
+            .filter(|day| find_param(day.params, name).is_some())
+            .collect();
+        if with_param.is_empty() {
+            bail!("None of the days to run has a parameter '{}'", name);
+        }
+        for day in with_param {
+            Params::default().set(day.params, name, *value)?;
+        }
+    }
+
+    let mut failures: usize = 0;
+    let parallel_parts = options.parallel_parts(days.len());
+
+    run_in_order(
+        &days,
+        options.jobs(),
+        |day| {
+            let mut out = vec![];
+            let ok = run_day(day, explicit, options, parallel_parts, &cache, &mut out);
+            (out, ok)
+        },
+        |(out, ok)| {
+            for line in out {
+                println!("{}", line);
+            }
+            if !ok {
+                failures += 1;
+            }
+        },
+    );
+
+    if failures > 0 {
+        bail!("{} day(s) failed", failures);
+    }
+
+    Ok(())
+}
+
+/// Apply `f` to every item on up to `jobs` scoped threads,
+/// handing the results to `emit` in the order of the items as soon as they are available
+pub fn run_in_order<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(R),
+) {
+    let next_item = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next_item, f) = (&next_item, &f);
+            scope.spawn(move || {
+                loop {
+                    let idx = next_item.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(idx) else {
+                        break;
+                    };
+                    if tx.send((idx, f(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_emit: usize = 0;
+        for (idx, result) in rx {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&next_emit) {
+                emit(result);
+                next_emit += 1;
+            }
+        }
+    });
+}
+
+fn run_day(
+    day: &Day,
+    explicit: bool,
+    options: &Options,
+    parallel_parts: bool,
+    cache: &Cache,
+    out: &mut Vec<String>,
+) -> bool {
+    if !explicit && !has_input(day.day) {
+        out.push(format!("Day {:0>2}: no input, skipped", day.day));
+        return true;
+    }
+
+    match read_input_raw(day.day) {
+        Ok(input) if options.check => {
+            check_day(day, &input, &day_params(day, &options.params), out)
+        }
+        Ok(input) => solve_day(day, &input, options, parallel_parts, cache, out),
+        Err(err) => {
+            out.push(format!("Day {:0>2}: {:#}", day.day, err));
+            false
+        }
+    }
+}
+
+fn check_day(day: &Day, input: &str, params: &Params, out: &mut Vec<String>) -> bool {
+    let problems = (day.check)(input, params);
+
+    if problems.is_empty() {
+        out.push(format!("Day {:0>2}: ok", day.day));
+    } else {
+        out.push(format!(
+            "Day {:0>2}: {} problem(s)",
+            day.day,
+            problems.len()
+        ));
+        for problem in &problems {
+            out.push(format!("  {}", problem));
+        }
+    }
+
+    problems.is_empty()
+}
+
+/// Solve a day using the parts and cache mode selected in `options`, the parts on their own
+/// threads with `parallel_parts`, writing the output lines to `out` and returning whether
+/// every part succeeded
+pub(crate) fn solve_day(
+    day: &Day,
+    input: &str,
+    options: &Options,
+    parallel_parts: bool,
+    cache: &Cache,
+    out: &mut Vec<String>,
+) -> bool {
+    let parts = options.parts();
+    let params = day_params(day, &options.params);
+    // Answers under other parameters would mix with the real ones in the cache
+    let use_cache = params == Params::default();
+    let input_hash = hash_input(input);
+    let key = |part: u8| CacheKey {
+        day: day.day,
+        part,
+        version: day.version,
+        input_hash,
+    };
+
+    // Cached answers would skip the solvers and with that their trace output
+    if options.cache == CacheMode::Use
+        && use_cache
+        && !trace::enabled(Level::Info)
+        && let Some(answers) = parts
+            .iter()
+            .map(|&part| cache.get(&key(part)))
+            .collect::<Option<Vec<String>>>()
+    {
+        out.push(format!("Day {:0>2} (cached)", day.day));
+        for (part, answer) in parts.iter().zip(answers) {
+            out.push(format!("  Part {}: {} (cached)", part, answer));
+        }
+        return true;
+    }
+
+    let report = match (day.solve)(input, parts, parallel_parts, &params) {
+        Ok(report) => report,
+        Err(err) => {
+            out.push(format!("Day {:0>2}: parsing failed: {:#}", day.day, err));
+            return false;
+        }
+    };
+
+    out.push(format!(
+        "Day {:0>2} (parsed in {:?})",
+        day.day, report.parse_elapsed
+    ));
+    let mut ok = true;
+
+    for part_report in &report.parts {
+        let part = part_report.part;
+        match &part_report.answer {
+            Ok(answer) => {
+                out.push(format!(
+                    "  Part {}: {} ({:?}{})",
+                    part,
+                    answer,
+                    part_report.elapsed,
+                    format_allocs(part_report.allocs)
+                ));
+                if options.cache != CacheMode::Bypass
+                    && use_cache
+                    && let Err(err) = cache.put(&key(part), answer)
+                {
+                    out.push(format!(
+                        "  Part {}: could not cache answer: {:#}",
+                        part, err
+                    ));
+                }
+            }
+            Err(err) => {
+                out.push(format!("  Part {}: error: {:#}", part, err));
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+/// The overrides in `overrides` that are parameters of `day`
+fn day_params(day: &Day, overrides: &[(String, usize)]) -> Params {
+    let mut params = Params::default();
+    for (name, value) in overrides {
+        // Only fails for parameters of other days
+        let _ = params.set(day.params, name, *value);
+    }
+
+    params
+}
+
+/// Allocation statistics appended to a part's timing, empty without the `alloc-count` feature
+pub(crate) fn format_allocs(allocs: Option<AllocStats>) -> String {
+    allocs.map_or_else(String::new, |allocs| {
+        format!(
+            ", {} allocations, {} allocated, {} peak",
+            allocs.allocations,
+            format_bytes(allocs.bytes),
+            format_bytes(allocs.peak)
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::Day01;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn parse_run_args(arguments: &[&str]) -> Options {
+        match parse_args(args(arguments)).unwrap() {
+            Command::Run(options) => options,
+            command => panic!("Expected a run command, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_days_and_check() {
+        assert_eq!(
+            parse_run_args(&["--check", "3", "1"]),
+            Options {
+                days: vec![3, 1],
+                check: true,
+                ..Options::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_cache_flags() {
+        assert_eq!(parse_run_args(&["--no-cache"]).cache, CacheMode::Bypass);
+        assert_eq!(
+            parse_run_args(&["--refresh-cache"]).cache,
+            CacheMode::Refresh
+        );
+        assert!(parse_run_args(&["--clear-cache"]).clear_cache);
+    }
+
+    #[test]
+    fn test_parse_args_watch() {
+        assert_eq!(
+            parse_args(args(&["watch", "7"])).unwrap(),
+            Command::Watch(7)
+        );
+        assert!(parse_args(args(&["watch"])).is_err());
+        assert!(parse_args(args(&["watch", "1", "2"])).is_err());
+        assert!(parse_args(args(&["watch", "99"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_serve() {
+        assert_eq!(
+            parse_args(args(&["serve"])).unwrap(),
+            Command::Serve {
+                addr: DEFAULT_ADDR.to_string(),
+                limits: Limits::default()
+            }
+        );
+        assert_eq!(
+            parse_args(args(&[
+                "serve",
+                "--addr",
+                "0.0.0.0:9000",
+                "--max-body",
+                "100",
+                "--timeout",
+                "0.5",
+                "--max-connections",
+                "2",
+                "--max-solvers",
+                "3"
+            ]))
+            .unwrap(),
+            Command::Serve {
+                addr: "0.0.0.0:9000".to_string(),
+                limits: Limits {
+                    max_body: 100,
+                    timeout: Duration::from_millis(500),
+                    max_connections: 2,
+                    max_solvers: 3
+                }
+            }
+        );
+        assert!(parse_args(args(&["serve", "--timeout", "0"])).is_err());
+        assert!(parse_args(args(&["serve", "--max-solvers", "0"])).is_err());
+        assert!(parse_args(args(&["serve", "8080"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_fetch() {
+        assert_eq!(
+            parse_args(args(&["fetch"])).unwrap(),
+            Command::Fetch {
+                days: vec![],
+                base_url: None,
+                session_file: DEFAULT_SESSION_FILE.to_string(),
+                interval: DEFAULT_INTERVAL,
+                force: false
+            }
+        );
+        assert_eq!(
+            parse_args(args(&[
+                "fetch",
+                "--base-url",
+                "http://localhost:1234",
+                "--session-file",
+                "token",
+                "--interval",
+                "0",
+                "--force",
+                "2",
+                "3"
+            ]))
+            .unwrap(),
+            Command::Fetch {
+                days: vec![2, 3],
+                base_url: Some("http://localhost:1234".to_string()),
+                session_file: "token".to_string(),
+                interval: Duration::ZERO,
+                force: true
+            }
+        );
+        assert!(parse_args(args(&["fetch", "--interval", "-1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_embedded() {
+        assert_eq!(parse_args(args(&["embedded"])).unwrap(), Command::Embedded);
+        assert!(parse_args(args(&["embedded", "1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_export() {
+        assert_eq!(
+            parse_args(args(&[
+                "export",
+                "1",
+                "zero-events",
+                "--param",
+                "dial_target=5",
+                "--input",
+                "scenario.txt"
+            ]))
+            .unwrap(),
+            Command::Export {
+                day: 1,
+                name: Some("zero-events".to_string()),
+                input: Some("scenario.txt".to_string()),
+                params: vec![("dial_target".to_string(), 5)]
+            }
+        );
+        assert!(parse_args(args(&["export"])).is_err());
+        assert!(parse_args(args(&["export", "1", "a", "b"])).is_err());
+    }
+
+    #[test]
+    fn test_export_checks_name() {
+        let error = export::<Day01>("R1\n", "unknown", &Params::default()).unwrap_err();
+
+        assert!(format!("{}", error).contains("available: zero-events"));
+    }
+
+    #[test]
+    fn test_parse_args_repl() {
+        assert_eq!(parse_args(args(&["repl"])).unwrap(), Command::Repl(1));
+        assert_eq!(parse_args(args(&["repl", "4"])).unwrap(), Command::Repl(4));
+        assert!(parse_args(args(&["repl", "1", "2"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_rejects_unknown_day_and_option() {
+        assert!(parse_args(args(&["99"])).is_err());
+        assert!(parse_args(args(&["x"])).is_err());
+        assert!(parse_args(args(&["--nope"])).is_err());
+    }
+
+    #[test]
+    fn test_solve_reports_both_parts() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        let report = solve::<Day01>(input, &[1, 2], false, &Params::default()).unwrap();
+        let answers: Vec<String> = report
+            .parts
+            .into_iter()
+            .map(|part| part.answer.unwrap())
+            .collect();
+
+        assert_eq!(answers, vec!["3", "6"]);
+    }
+
+    #[test]
+    fn test_solve_only_selected_part() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        let report = solve::<Day01>(input, &[2], false, &Params::default()).unwrap();
+
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].part, 2);
+        assert_eq!(report.parts[0].answer.as_ref().unwrap(), "6");
+    }
+
+    #[test]
+    fn test_parse_args_part() {
+        assert_eq!(parse_run_args(&["--part", "2", "4"]).parts(), &[2]);
+        assert_eq!(parse_run_args(&["4"]).parts(), &[1, 2]);
+        assert!(parse_args(args(&["--part", "3"])).is_err());
+        assert!(parse_args(args(&["--part"])).is_err());
+    }
+
+    #[test]
+    fn test_parallel_parts_stay_within_jobs() {
+        let options = |jobs: usize, part: Option<u8>| Options {
+            jobs: Some(jobs),
+            part,
+            ..Options::default()
+        };
+
+        assert!(options(2, None).parallel_parts(1));
+        assert!(!options(1, None).parallel_parts(1));
+        assert!(!options(4, Some(1)).parallel_parts(1));
+        assert!(options(14, None).parallel_parts(7));
+        assert!(!options(8, None).parallel_parts(7));
+        assert!(!options(4, None).parallel_parts(7));
+        assert!(options(4, None).parallel_parts(2));
+    }
+
+    #[test]
+    fn test_solve_parallel_parts_keeps_part_order() {
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        let report = solve::<Day01>(input, &[1, 2], true, &Params::default()).unwrap();
+        let answers: Vec<(u8, String)> = report
+            .parts
+            .into_iter()
+            .map(|part| (part.part, part.answer.unwrap()))
+            .collect();
+
+        assert_eq!(answers, vec![(1, "3".to_string()), (2, "6".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_args_readme() {
+        assert_eq!(
+            parse_args(args(&["readme"])).unwrap(),
+            Command::Readme {
+                path: "README.md".to_string(),
+                samples: DEFAULT_SAMPLES,
+            }
+        );
+        assert_eq!(
+            parse_args(args(&["readme", "--samples", "3", "OTHER.md"])).unwrap(),
+            Command::Readme {
+                path: "OTHER.md".to_string(),
+                samples: 3,
+            }
+        );
+        assert!(parse_args(args(&["readme", "--samples", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_bench() {
+        assert_eq!(
+            parse_args(args(&["bench", "--samples", "5", "2"])).unwrap(),
+            Command::Bench {
+                days: vec![2],
+                samples: 5,
+                history: DEFAULT_HISTORY_PATH.to_string(),
+            }
+        );
+        assert_eq!(
+            parse_args(args(&[
+                "bench",
+                "compare",
+                "--baseline",
+                "abc123",
+                "--threshold",
+                "25",
+                "--history",
+                "h.csv"
+            ]))
+            .unwrap(),
+            Command::Compare {
+                history: "h.csv".to_string(),
+                baseline: Some("abc123".to_string()),
+                threshold: 0.25,
+            }
+        );
+        assert!(parse_args(args(&["bench", "compare", "--threshold", "x"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_verbosity() {
+        assert_eq!(parse_run_args(&["-v"]).verbosity, 1);
+        assert_eq!(parse_run_args(&["-vv", "-v", "1"]).verbosity, 3);
+        assert!(parse_args(args(&["-vx"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_params() {
+        assert_eq!(
+            parse_run_args(&["--param", "dial_target=5", "--param", "dial_start=0", "1"]).params,
+            vec![
+                ("dial_target".to_string(), 5),
+                ("dial_start".to_string(), 0)
+            ]
+        );
+        assert!(parse_args(args(&["--param", "dial_target"])).is_err());
+        assert!(parse_args(args(&["--param", "dial_target=x"])).is_err());
+        assert_eq!(
+            parse_run_args(&["--param", "carry=true", "--param", "dial_length.2=12", "1"]).params,
+            vec![("carry".to_string(), 1), ("dial_length.2".to_string(), 12)]
+        );
+    }
+
+    #[test]
+    fn test_day_params_only_applies_the_days_parameters() {
+        let overrides = vec![("dial_target".to_string(), 5), ("threshold".to_string(), 3)];
+        let params = day_params(find_day(1).unwrap(), &overrides);
+
+        assert_ne!(params, Params::default());
+        assert_eq!(
+            day_params(find_day(2).unwrap(), &overrides),
+            Params::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_args_jobs() {
+        assert_eq!(parse_run_args(&["--jobs", "3"]).jobs(), 3);
+        assert!(parse_args(args(&["--jobs", "0"])).is_err());
+        assert!(parse_args(args(&["--jobs", "x"])).is_err());
+    }
+
+    #[test]
+    fn test_run_in_order_emits_in_item_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut emitted = vec![];
+
+        run_in_order(
+            &items,
+            4,
+            |&x| {
+                // Make the early items finish last
+                thread::sleep(Duration::from_millis(20 - x));
+                x * 2
+            },
+            |result| emitted.push(result),
+        );
+
+        assert_eq!(emitted, items.iter().map(|x| x * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_run_in_order_handles_no_items() {
+        let mut emitted: Vec<u8> = vec![];
+
+        run_in_order(&[] as &[u8], 4, |&x| x, |result| emitted.push(result));
+
+        assert!(emitted.is_empty());
+    }
+}
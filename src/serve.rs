@@ -0,0 +1,638 @@
+//! Minimal HTTP/1.1 service exposing the solvers: `POST /day/<n>/part/<p>` with the puzzle
+//! input as the body answers with the answer and timings as JSON.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use crate::days::{Day, find_day};
+use crate::runner::DayReport;
+use crate::solution::Params;
+
+/// Address the server listens on unless told otherwise
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+/// Largest accepted request body unless told otherwise
+pub const DEFAULT_MAX_BODY: usize = 1024 * 1024;
+/// Time a request may take to arrive and to be solved unless told otherwise
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Most connections handled at once unless told otherwise
+pub const DEFAULT_MAX_CONNECTIONS: usize = 64;
+/// Most solvers running at once unless told otherwise
+pub const DEFAULT_MAX_SOLVERS: usize = 8;
+
+/// Longest accepted request or header line
+const MAX_LINE: u64 = 8 * 1024;
+/// Most headers accepted in a single request
+const MAX_HEADERS: usize = 100;
+/// Time spent answering a connection that is over the limit
+const REJECT_TIMEOUT: Duration = Duration::from_millis(10);
+/// Most connections over the limit waiting for their 503, further ones being closed at once
+const MAX_REJECTS: usize = 64;
+
+/// Limits applied to each request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Largest accepted body in bytes
+    pub max_body: usize,
+    /// Time allowed for receiving the request, and separately for solving it
+    pub timeout: Duration,
+    /// Most connections handled at once, further ones being answered with 503
+    pub max_connections: usize,
+    /// Most solvers running at once, those that timed out included since they can't be
+    /// stopped. Further requests are answered with 503.
+    pub max_solvers: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_body: DEFAULT_MAX_BODY,
+            timeout: DEFAULT_TIMEOUT,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            max_solvers: DEFAULT_MAX_SOLVERS,
+        }
+    }
+}
+
+/// A bounded number of slots for threads of one kind
+#[derive(Debug, Clone)]
+struct Slots {
+    used: Arc<AtomicUsize>,
+    limit: usize,
+}
+
+impl Slots {
+    fn new(limit: usize) -> Self {
+        Slots {
+            used: Arc::new(AtomicUsize::new(0)),
+            limit,
+        }
+    }
+
+    /// Take a slot, released when the returned guard is dropped, or `None` if all are taken
+    fn try_acquire(&self) -> Option<Slot> {
+        self.used
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used| {
+                (used < self.limit).then_some(used + 1)
+            })
+            .ok()
+            .map(|_| Slot(Arc::clone(&self.used)))
+    }
+}
+
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Solves a part of an input for a day, the day's own solver unless a test swaps it
+type Solver = Arc<dyn Fn(&Day, &str, u8) -> Result<DayReport> + Send + Sync>;
+
+pub struct Server {
+    listener: TcpListener,
+    limits: Limits,
+    connections: Slots,
+    solvers: Slots,
+    solver: Solver,
+}
+
+impl Server {
+    /// Listen on `addr`, which may use port 0 to pick any free port
+    pub fn bind(addr: &str, limits: Limits) -> Result<Self> {
+        let listener =
+            TcpListener::bind(addr).with_context(|| format!("Could not listen on {}", addr))?;
+        Ok(Server {
+            listener,
+            limits,
+            connections: Slots::new(limits.max_connections),
+            solvers: Slots::new(limits.max_solvers),
+            solver: Arc::new(|day, input, part| {
+                (day.solve)(input, &[part], false, &Params::default())
+            }),
+        })
+    }
+
+    /// Server answering with `solver` instead of the days' own solvers
+    #[cfg(test)]
+    fn with_solver(self, solver: Solver) -> Self {
+        Server { solver, ..self }
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Answer requests forever, each connection on its own thread
+    pub fn run(self) -> Result<()> {
+        // Connections over the limit are answered on a single thread of their own, keeping
+        // the accepting thread from ever waiting on a client
+        let (rejects, rejected) = mpsc::sync_channel::<TcpStream>(MAX_REJECTS);
+        thread::spawn(move || {
+            for stream in rejected {
+                reject(&stream);
+            }
+        });
+
+        for stream in self.listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let Some(slot) = self.connections.try_acquire() else {
+                // Dropping the stream when the rejecting thread is behind closes it at once
+                let _ = rejects.try_send(stream);
+                continue;
+            };
+            let limits = self.limits;
+            let solvers = self.solvers.clone();
+            let solver = Arc::clone(&self.solver);
+            thread::spawn(move || {
+                handle_connection(stream, limits, &solvers, &solver);
+                drop(slot);
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Answer a connection over the limit with 503 without giving it a thread of its own
+fn reject(stream: &TcpStream) {
+    let _ = stream.set_write_timeout(Some(REJECT_TIMEOUT));
+    let _ = Response::error(503, "Too many connections").write_to(&mut &*stream);
+    let _ = stream.shutdown(Shutdown::Write);
+
+    // Closing with unread data resets the connection, which can discard the response before
+    // the client reads it, so read what already arrived first
+    let _ = stream.set_read_timeout(Some(REJECT_TIMEOUT));
+    let _ = io::copy(&mut stream.take(MAX_LINE), &mut io::sink());
+}
+
+/// Serve the solvers on `addr` until the process is stopped
+pub fn serve(addr: &str, limits: Limits) -> Result<()> {
+    let server = Server::bind(addr, limits)?;
+    println!("Listening on http://{}", server.local_addr()?);
+    server.run()
+}
+
+/// Status and JSON body of a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: impl AsRef<str>) -> Self {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message.as_ref())),
+        }
+    }
+
+    fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            self.body.len(),
+            self.body
+        )?;
+        out.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+/// A request that was received in full
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+fn handle_connection(stream: TcpStream, limits: Limits, solvers: &Slots, solver: &Solver) {
+    // Bounds how long a slow client can keep the connection open, however it spaces its bytes
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + limits.timeout,
+    });
+
+    let response = match read_request(&mut reader, limits.max_body) {
+        Ok(request) => respond(&request, limits.timeout, solvers, solver),
+        Err(response) => response,
+    };
+    let _ = response.write_to(&mut &stream);
+}
+
+/// Reads from a stream, failing with `TimedOut` once the deadline has passed
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        // The timeout of a socket applies to each read, so it is shortened before every one
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, Response> {
+    let request_line = read_line(reader)?;
+    let mut words = request_line.split_whitespace();
+    let (Some(method), Some(path), Some(_version)) = (words.next(), words.next(), words.next())
+    else {
+        return Err(Response::error(400, "Malformed request line"));
+    };
+
+    let mut content_length = None;
+    let mut header_count = 0;
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        header_count += 1;
+        if header_count > MAX_HEADERS {
+            return Err(Response::error(431, "Too many headers"));
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(Response::error(400, "Malformed header"));
+        };
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| Response::error(400, "Malformed Content-Length"))?;
+            content_length = Some(length);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(411, "Chunked bodies are not supported"));
+        }
+    }
+
+    let body = match content_length {
+        Some(length) if length > max_body => {
+            return Err(Response::error(
+                413,
+                format!("Body should be at most {} bytes", max_body),
+            ));
+        }
+        Some(length) => {
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).map_err(io_error)?;
+            String::from_utf8(body).map_err(|_| Response::error(400, "Body should be UTF-8"))?
+        }
+        None if method == "POST" => return Err(Response::error(411, "Content-Length required")),
+        None => String::new(),
+    };
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    })
+}
+
+/// Read a CRLF or LF terminated line without its terminator
+fn read_line(reader: &mut impl BufRead) -> Result<String, Response> {
+    let mut line = String::new();
+    reader
+        .take(MAX_LINE)
+        .read_line(&mut line)
+        .map_err(io_error)?;
+
+    if !line.ends_with('\n') {
+        return Err(if line.len() as u64 == MAX_LINE {
+            Response::error(431, "Line too long")
+        } else {
+            Response::error(400, "Request ended early")
+        });
+    }
+    line.truncate(line.trim_end_matches(['\r', '\n']).len());
+
+    Ok(line)
+}
+
+fn io_error(err: io::Error) -> Response {
+    match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            Response::error(408, "Timed out reading the request")
+        }
+        io::ErrorKind::UnexpectedEof => Response::error(400, "Request ended early"),
+        _ => Response::error(400, err.to_string()),
+    }
+}
+
+fn respond(request: &Request, timeout: Duration, solvers: &Slots, solver: &Solver) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let ["day", day, "part", part] = segments[..] else {
+        return Response::error(404, format!("No route for {}", request.path));
+    };
+    let (Some(day), Ok(part @ 1..=2)) = (
+        day.parse::<u8>().ok().and_then(find_day),
+        part.parse::<u8>(),
+    ) else {
+        return Response::error(404, format!("No solver for {}", request.path));
+    };
+    if request.method != "POST" {
+        return Response::error(405, "Use POST with the puzzle input as the body");
+    }
+
+    let Some(slot) = solvers.try_acquire() else {
+        return Response::error(503, "Too many solvers running, try again later");
+    };
+    let input = request.body.clone();
+    let solver = Arc::clone(solver);
+    let report = match run_with_timeout(timeout, move || {
+        let report = solver(day, &input, part);
+        // Held until the solver is done, even after the request timed out
+        drop(slot);
+        report
+    }) {
+        Ok(report) => report,
+        Err(RecvTimeoutError::Timeout) => {
+            return Response::error(504, format!("Solving took longer than {:?}", timeout));
+        }
+        Err(RecvTimeoutError::Disconnected) => return Response::error(500, "Solver panicked"),
+    };
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => return Response::error(422, format!("Parsing failed: {:#}", err)),
+    };
+    let Some(part_report) = report.parts.first() else {
+        return Response::error(500, "Solver returned no answer");
+    };
+
+    match &part_report.answer {
+        Ok(answer) => Response {
+            status: 200,
+            body: format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ns\":{},\"solve_ns\":{}}}",
+                day.day,
+                part,
+                json_string(answer),
+                report.parse_elapsed.as_nanos(),
+                part_report.elapsed.as_nanos()
+            ),
+        },
+        Err(err) => Response::error(422, format!("{:#}", err)),
+    }
+}
+
+/// Run `f` on its own thread, giving up on it after `timeout`. A solver that times out
+/// can't be stopped, so its thread keeps running in the background until it finishes.
+fn run_with_timeout<R: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> R + Send + 'static,
+) -> Result<R, RecvTimeoutError> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(f());
+    });
+
+    receiver.recv_timeout(timeout)
+}
+
+/// Quote a string for JSON
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use anyhow::bail;
+
+    use super::*;
+
+    /// Start a server on a free localhost port, returning its address
+    fn start(limits: Limits) -> SocketAddr {
+        run(Server::bind("127.0.0.1:0", limits).unwrap())
+    }
+
+    fn run(server: Server) -> SocketAddr {
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        addr
+    }
+
+    fn send(addr: SocketAddr, request: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+        (status, body)
+    }
+
+    fn post(path: &str, body: &str) -> String {
+        format!(
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            path,
+            body.len(),
+            body
+        )
+    }
+
+    #[test]
+    fn test_serve_answers_with_json() {
+        let addr = start(Limits::default());
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+        let (status, body) = send(addr, &post("/day/1/part/2", input));
+
+        assert_eq!(status, 200);
+        assert!(body.starts_with("{\"day\":1,\"part\":2,\"answer\":\"6\",\"parse_ns\":"));
+    }
+
+    #[test]
+    fn test_serve_rejects_bad_requests() {
+        let addr = start(Limits {
+            max_body: 16,
+            timeout: Duration::from_secs(5),
+            ..Limits::default()
+        });
+
+        assert_eq!(send(addr, &post("/day/99/part/1", "")).0, 404);
+        assert_eq!(send(addr, &post("/day/1/part/3", "")).0, 404);
+        assert_eq!(send(addr, &post("/nope", "")).0, 404);
+        assert_eq!(send(addr, "GET /day/1/part/1 HTTP/1.1\r\n\r\n").0, 405);
+        assert_eq!(send(addr, "POST /day/1/part/1 HTTP/1.1\r\n\r\n").0, 411);
+        assert_eq!(
+            send(addr, &post("/day/1/part/1", &"R1\n".repeat(10))).0,
+            413
+        );
+        assert_eq!(send(addr, &post("/day/1/part/1", "X1\n")).0, 422);
+    }
+
+    #[test]
+    fn test_serve_times_out_slow_clients() {
+        let addr = start(Limits {
+            max_body: 16,
+            timeout: Duration::from_millis(50),
+            ..Limits::default()
+        });
+
+        let (status, body) = send(
+            addr,
+            "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 5\r\n\r\nR1",
+        );
+
+        assert_eq!(status, 408);
+        assert!(body.contains("Timed out"));
+    }
+
+    #[test]
+    fn test_serve_times_out_trickling_clients() {
+        let addr = start(Limits {
+            max_body: 16,
+            timeout: Duration::from_millis(300),
+            ..Limits::default()
+        });
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let started = Instant::now();
+
+        // A byte every 100ms never hits a per-read timeout of 300ms
+        thread::spawn(move || {
+            for byte in b"POST /day/1/part/1 HTTP/1.1\r\n".iter().cycle() {
+                if writer.write_all(&[*byte]).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+
+        assert!(response.starts_with("HTTP/1.1 408"), "{}", response);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_serve_limits_connections() {
+        let addr = start(Limits {
+            max_connections: 1,
+            ..Limits::default()
+        });
+
+        // Accepted first, so it holds the only connection for as long as it stays open
+        let _idle = TcpStream::connect(addr).unwrap();
+        let (status, body) = send(addr, &post("/day/1/part/1", "R1\n"));
+
+        assert_eq!(status, 503);
+        assert!(body.contains("Too many connections"));
+    }
+
+    #[test]
+    fn test_serve_limits_solvers() {
+        let (started, solving) = mpsc::channel();
+        let (release, released) = mpsc::channel::<()>();
+        let released = Mutex::new(released);
+        let server = Server::bind(
+            "127.0.0.1:0",
+            Limits {
+                timeout: Duration::from_millis(300),
+                max_solvers: 1,
+                ..Limits::default()
+            },
+        )
+        .unwrap()
+        .with_solver(Arc::new(move |_, _, _| {
+            started.send(()).unwrap();
+            let _ = released.lock().unwrap().recv();
+            bail!("Released")
+        }));
+        let addr = run(server);
+
+        // The only solver stays busy after its request timed out, until it is released
+        let timed_out = thread::spawn(move || send(addr, &post("/day/1/part/2", "R1\n")));
+        solving.recv().unwrap();
+        let (status, body) = send(addr, &post("/day/1/part/1", "R1\n"));
+        assert_eq!(status, 503);
+        assert!(body.contains("Too many solvers"));
+        assert_eq!(timed_out.join().unwrap().0, 504);
+
+        release.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_slots_are_released() {
+        let slots = Slots::new(1);
+        let slot = slots.try_acquire();
+
+        assert!(slot.is_some());
+        assert!(slots.try_acquire().is_none());
+        drop(slot);
+        assert!(slots.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        assert_eq!(run_with_timeout(Duration::from_secs(5), || 42), Ok(42));
+        assert_eq!(
+            run_with_timeout(Duration::from_millis(10), || thread::sleep(
+                Duration::from_secs(1)
+            )),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn test_json_string_escapes() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt::Debug;
+
+use anyhow::{Result, bail};
+
+use crate::check::Problem;
+
+/// A single day's puzzle, split into parsing and solving both parts
+pub trait Solution {
+    /// Parsed puzzle input shared by both parts, possibly solved on separate threads
+    type Input: Sync + Debug;
+
+    /// Version of the solver, bump it when a change could alter the answers
+    /// so that previously cached answers are no longer used
+    const VERSION: u32 = 1;
+
+    /// Tunable constants of the solver, passed to `part1_with` and `part2_with`
+    const PARAMS: &'static [Param] = &[];
+
+    /// Names of the extra outputs `export` can produce besides the answers, e.g. traces
+    const EXPORTS: &'static [&'static str] = &[];
+
+    /// Parse the raw contents of the input file
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<String>;
+
+    fn part2(input: &Self::Input) -> Result<String>;
+
+    /// Solve part 1 with parameter values that may differ from the defaults
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<String> {
+        Self::part1(input)
+    }
+
+    /// Solve part 2 with parameter values that may differ from the defaults
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<String> {
+        Self::part2(input)
+    }
+
+    /// Up to `limit` lines describing the solver's intermediate state, for the REPL's `inspect`
+    fn inspect(_input: &Self::Input, _params: &Params, _limit: usize) -> Result<Vec<String>> {
+        bail!("Nothing to inspect besides the parsed input, try `show`")
+    }
+
+    /// Produce the export called `name`, one of `EXPORTS`
+    fn export(_input: &Self::Input, name: &str, _params: &Params) -> Result<String> {
+        bail!("Unknown export '{}'", name)
+    }
+
+    /// Validate the raw contents of the input file against the day's grammar,
+    /// reporting every problem found instead of stopping at the first one
+    fn check(input: &str) -> Vec<Problem>;
+
+    /// Validate the raw contents of the input file with parameter values that may change
+    /// the grammar, e.g. the base numbers are written in
+    fn check_with(input: &str, _params: &Params) -> Vec<Problem> {
+        Self::check(input)
+    }
+}
+
+/// A constant of a solver that can be changed without recompiling, e.g. from the REPL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: usize,
+    pub description: &'static str,
+    pub kind: ParamKind,
+}
+
+/// Values a `Param` takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    Number,
+    /// 0 or 1, also written `false` or `true`
+    Flag,
+    /// A number that can also be set for the `n`th of several items alone, as `NAME.n` with
+    /// `n` counted from 1
+    PerItem,
+}
+
+/// The one of `params` that `name` sets, with the item it is set for alone if any
+pub fn find_param<'a>(params: &'a [Param], name: &str) -> Option<(&'a Param, Option<usize>)> {
+    let (name, item) = match name.split_once('.') {
+        Some((name, item)) => (name, Some(item.parse().ok()?)),
+        None => (name, None),
+    };
+    let param = params.iter().find(|param| param.name == name)?;
+
+    Some((param, item))
+}
+
+/// Parse the value of a parameter, a number or `false` or `true` for 0 or 1
+pub fn parse_value(value: &str) -> Option<usize> {
+    match value {
+        "false" => Some(0),
+        "true" => Some(1),
+        value => value.parse().ok(),
+    }
+}
+
+/// Parameter values overriding the defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    /// Name, item set alone if any and value of each override
+    values: Vec<(&'static str, Option<usize>, usize)>,
+}
+
+impl Params {
+    /// Value of `param`, its default unless it was set
+    pub fn get(&self, param: &Param) -> usize {
+        self.find(param, None).unwrap_or(param.default)
+    }
+
+    /// Value of `param` for item `n`, counted from 1, the value of every item unless it was
+    /// set for that item alone
+    pub fn get_nth(&self, param: &Param, n: usize) -> usize {
+        self.find(param, Some(n)).unwrap_or_else(|| self.get(param))
+    }
+
+    /// Value of a `Flag` parameter
+    pub fn flag(&self, param: &Param) -> bool {
+        self.get(param) != 0
+    }
+
+    /// Items `param` was set for alone, with their values, in order
+    pub fn items(&self, param: &Param) -> Vec<(usize, usize)> {
+        let mut items: Vec<(usize, usize)> = self
+            .values
+            .iter()
+            .filter(|(name, _, _)| *name == param.name)
+            .filter_map(|&(_, item, value)| Some((item?, value)))
+            .collect();
+        items.sort_unstable();
+
+        items
+    }
+
+    fn find(&self, param: &Param, item: Option<usize>) -> Option<usize> {
+        self.values
+            .iter()
+            .find(|&&(name, i, _)| name == param.name && i == item)
+            .map(|&(_, _, value)| value)
+    }
+
+    /// Override the value of the parameter called `name`, which has to be one of `params`,
+    /// possibly for a single item as `NAME.n`
+    pub fn set(&mut self, params: &[Param], name: &str, value: usize) -> Result<()> {
+        let Some((param, item)) = find_param(params, name) else {
+            bail!("Unknown parameter '{}'", name);
+        };
+        match (param.kind, item) {
+            (ParamKind::Flag, _) if value > 1 => {
+                bail!("Parameter '{}' should be 0 or 1, got {}", name, value)
+            }
+            (ParamKind::PerItem, Some(0)) => {
+                bail!("Items of parameter '{}' are numbered from 1", param.name)
+            }
+            (ParamKind::Number | ParamKind::Flag, Some(_)) => {
+                bail!("Parameter '{}' can't be set for a single item", param.name)
+            }
+            _ => {}
+        }
+        self.values
+            .retain(|&(name, i, _)| name != param.name || i != item);
+        self.values.push((param.name, item, value));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGITS: Param = Param {
+        name: "digits",
+        default: 12,
+        description: "Digits picked",
+        kind: ParamKind::Number,
+    };
+
+    const SIZE: Param = Param {
+        name: "size",
+        default: 10,
+        description: "Size of each item",
+        kind: ParamKind::PerItem,
+    };
+
+    const FAST: Param = Param {
+        name: "fast",
+        default: 0,
+        description: "Whether to hurry",
+        kind: ParamKind::Flag,
+    };
+
+    #[test]
+    fn test_params_default_until_set() {
+        let mut params = Params::default();
+        assert_eq!(params.get(&DIGITS), 12);
+
+        params.set(&[DIGITS], "digits", 3).unwrap();
+        params.set(&[DIGITS], "digits", 4).unwrap();
+        assert_eq!(params.get(&DIGITS), 4);
+
+        assert!(params.set(&[DIGITS], "other", 1).is_err());
+        assert!(params.set(&[DIGITS], "digits.2", 1).is_err());
+    }
+
+    #[test]
+    fn test_params_per_item_and_flags() {
+        let mut params = Params::default();
+        params.set(&[SIZE, FAST], "size.2", 7).unwrap();
+        assert_eq!(
+            (params.get_nth(&SIZE, 1), params.get_nth(&SIZE, 2)),
+            (10, 7)
+        );
+
+        params.set(&[SIZE, FAST], "size", 3).unwrap();
+        params.set(&[SIZE, FAST], "size.2", 8).unwrap();
+        assert_eq!((params.get_nth(&SIZE, 1), params.get_nth(&SIZE, 2)), (3, 8));
+        assert_eq!(params.items(&SIZE), vec![(2, 8)]);
+        assert!(params.set(&[SIZE, FAST], "size.0", 1).is_err());
+        assert!(params.set(&[SIZE, FAST], "size.x", 1).is_err());
+
+        assert!(!params.flag(&FAST));
+        params
+            .set(&[SIZE, FAST], "fast", parse_value("true").unwrap())
+            .unwrap();
+        assert!(params.flag(&FAST));
+        assert!(params.set(&[SIZE, FAST], "fast", 2).is_err());
+        assert_eq!(parse_value("12"), Some(12));
+        assert_eq!(parse_value("yes"), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! Verbose output of intermediate solver state, written to stderr so that it doesn't mix
+//! with the answers. Use the `trace!` macro with a `Level`; events above the level set with
+//! `set_level` are skipped without formatting their arguments. Events of a part run with
+//! `in_part` name the part, as both parts of a day may run at once.
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+thread_local! {
+    /// Part being solved on this thread, if any
+    static PART: Cell<Option<u8>> = const { Cell::new(None) };
+}
+
+/// Verbosity of trace events, from least to most verbose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off = 0,
+    /// A few events per part, e.g. results of the main steps
+    Info = 1,
+    /// An event per input item, e.g. per line or row
+    Debug = 2,
+    /// Everything, e.g. per cell or per step of an inner loop
+    Trace = 3,
+}
+
+impl Level {
+    /// Level matching the number of `-v` flags given, saturating at `Trace`
+    pub fn from_verbosity(verbosity: u8) -> Self {
+        match verbosity {
+            0 => Level::Off,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::from_verbosity(LEVEL.load(Ordering::Relaxed))
+}
+
+/// Whether events of the given level are currently written
+pub fn enabled(level: Level) -> bool {
+    enabled_at(level, self::level())
+}
+
+/// Whether events of `level` are written when the level set is `threshold`
+fn enabled_at(level: Level, threshold: Level) -> bool {
+    level != Level::Off && level <= threshold
+}
+
+/// Run `f` with the events it writes on this thread naming `part`
+pub fn in_part<T>(part: u8, f: impl FnOnce() -> T) -> T {
+    let outer = PART.replace(Some(part));
+    let result = f();
+    PART.set(outer);
+    result
+}
+
+/// Last segment of the module an event came from, and the part being solved if any
+fn prefix(module_path: &str) -> String {
+    let module = module_path.rsplit("::").next().unwrap_or(module_path);
+    match PART.get() {
+        Some(part) => format!("{} part {}", module, part),
+        None => module.to_string(),
+    }
+}
+
+/// Write an event, prefixed with the module it came from and the part being solved.
+/// Called by the `trace!` macro after checking that the level is enabled.
+pub fn emit(module_path: &str, args: fmt::Arguments) {
+    eprintln!("[{}] {}", prefix(module_path), args);
+}
+
+/// Write a trace event if `level` is enabled, e.g. `trace!(Level::Debug, "dial at {}", dial)`
+#[macro_export]
+macro_rules! trace {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::trace::enabled($level) {
+            $crate::trace::emit(module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_from_verbosity() {
+        assert_eq!(Level::from_verbosity(0), Level::Off);
+        assert_eq!(Level::from_verbosity(2), Level::Debug);
+        assert_eq!(Level::from_verbosity(9), Level::Trace);
+    }
+
+    #[test]
+    fn test_enabled_up_to_level() {
+        assert!(enabled_at(Level::Info, Level::Debug));
+        assert!(enabled_at(Level::Debug, Level::Debug));
+        assert!(!enabled_at(Level::Trace, Level::Debug));
+        assert!(!enabled_at(Level::Off, Level::Debug));
+        assert!(!enabled_at(Level::Info, Level::Off));
+    }
+
+    #[test]
+    fn test_prefix_names_the_part() {
+        let module = "advent_of_code_2025::days::day01";
+
+        assert_eq!(prefix(module), "day01");
+        in_part(2, || {
+            assert_eq!(prefix(module), "day01 part 2");
+            in_part(1, || assert_eq!(prefix(module), "day01 part 1"));
+            assert_eq!(prefix(module), "day01 part 2");
+        });
+        assert_eq!(prefix(module), "day01");
+    }
+}
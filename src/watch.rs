@@ -0,0 +1,159 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+
+use crate::cache::{Cache, DEFAULT_CACHE_DIR};
+use crate::days::{Day, find_day};
+use crate::runner::{CacheMode, Options, solve_day};
+use crate::{example_path, input_path};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Modification time and size of a file, `None` if it doesn't exist
+type FileState = Option<(SystemTime, u64)>;
+
+/// Detects changes to a set of files by polling their metadata
+pub struct Watcher {
+    files: Vec<(PathBuf, FileState)>,
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Watcher {
+            files: paths.into_iter().map(|path| (path.into(), None)).collect(),
+        }
+    }
+
+    /// Return the files that were created, modified or removed since the last poll.
+    /// The first poll reports every existing file.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = vec![];
+
+        for (path, last_state) in &mut self.files {
+            let state = file_state(path);
+            if state != *last_state {
+                *last_state = state;
+                changed.push(path.clone());
+            }
+        }
+
+        changed
+    }
+
+    /// Watched files that currently exist
+    pub fn existing(&self) -> impl Iterator<Item = &Path> {
+        self.files
+            .iter()
+            .filter(|(_, state)| state.is_some())
+            .map(|(path, _)| path.as_path())
+    }
+}
+
+fn file_state(path: &Path) -> FileState {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Solve `day` for its input and example files every time either of them changes.
+/// Errors are printed and watching continues until the process is interrupted.
+pub fn watch(day: u8) -> Result<()> {
+    let day = find_day(day).with_context(|| format!("Day {} is not available", day))?;
+    let cache = Cache::new(DEFAULT_CACHE_DIR);
+    let mut watcher = Watcher::new([input_path(day.day), example_path(day.day)]);
+
+    println!(
+        "Watching {} and {}, press Ctrl-C to stop",
+        input_path(day.day),
+        example_path(day.day)
+    );
+
+    loop {
+        let changed = watcher.poll();
+        if !changed.is_empty() {
+            for path in &changed {
+                println!("\n[watch] {} changed", path.display());
+            }
+            solve_files(day, &watcher, &cache);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn solve_files(day: &Day, watcher: &Watcher, cache: &Cache) {
+    let mut any = false;
+
+    for path in watcher.existing() {
+        any = true;
+        println!("== {} ==", path.display());
+        match fs::read_to_string(path) {
+            // Always solve from scratch so that the timings are fresh
+            Ok(input) => {
+                let options = Options {
+                    cache: CacheMode::Bypass,
+                    ..Options::default()
+                };
+                let mut out = vec![];
+                let parallel_parts = options.parallel_parts(1);
+                solve_day(day, &input, &options, parallel_parts, cache, &mut out);
+                for line in out {
+                    println!("{}", line);
+                }
+            }
+            Err(err) => println!("Could not read file: {}", err),
+        }
+    }
+
+    if !any {
+        println!("Waiting for the input files to be created");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("aoc-watch-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_watcher_reports_existing_files_on_first_poll() {
+        let existing = temp_file("first-existing");
+        let missing = temp_file("first-missing");
+        fs::write(&existing, "L68").unwrap();
+        let mut watcher = Watcher::new([&existing, &missing]);
+
+        assert_eq!(watcher.poll(), vec![existing.clone()]);
+        assert_eq!(watcher.poll(), Vec::<PathBuf>::new());
+        assert_eq!(
+            watcher.existing().collect::<Vec<_>>(),
+            vec![existing.as_path()]
+        );
+        fs::remove_file(&existing).unwrap();
+    }
+
+    #[test]
+    fn test_watcher_reports_modified_created_and_removed_files() {
+        let path = temp_file("modified");
+        let mut watcher = Watcher::new([&path]);
+        assert_eq!(watcher.poll(), Vec::<PathBuf>::new());
+
+        fs::write(&path, "L68").unwrap();
+        assert_eq!(watcher.poll(), vec![path.clone()]);
+
+        // Size changes are noticed even if the modification time has a coarse resolution
+        fs::write(&path, "L68\nR48").unwrap();
+        assert_eq!(watcher.poll(), vec![path.clone()]);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.poll(), vec![path.clone()]);
+        assert_eq!(watcher.existing().count(), 0);
+    }
+}
@@ -0,0 +1,45 @@
+/* Calls the solvers through the C interface, exiting with 1 if anything is off. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+typedef int32_t (*solver)(const uint8_t *, size_t, char **);
+
+static int failures = 0;
+
+static void expect(const char *name, solver solve, const char *input, int32_t status,
+                   const char *answer) {
+    char *out = NULL;
+    int32_t got = solve((const uint8_t *)input, strlen(input), &out);
+
+    if (got != status || (answer != NULL && (out == NULL || strcmp(out, answer) != 0))) {
+        printf("FAIL %s: status %d, output '%s'\n", name, got, out ? out : "(null)");
+        failures++;
+    } else {
+        printf("ok   %s: %s\n", name, out ? out : "(null)");
+    }
+    aoc_free_string(out);
+}
+
+int main(void) {
+    const char *day01 = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+    const char *day03 = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+    char *out = NULL;
+
+    expect("day01 part1", aoc_day01_part1, day01, AOC_OK, "3");
+    expect("day01 part2", aoc_day01_part2, day01, AOC_OK, "6");
+    expect("day03 part1", aoc_day03_part1, day03, AOC_OK, "357");
+    expect("day03 part2", aoc_day03_part2, day03, AOC_OK, "3121910778619");
+    expect("day01 bad input", aoc_day01_part1, "X1\n", AOC_ERR_SOLVE, NULL);
+    expect("day01 bad utf-8", aoc_day01_part1, "\xff", AOC_ERR_UTF8, NULL);
+
+    if (aoc_day01_part1(NULL, 1, &out) != AOC_ERR_NULL || out != NULL) {
+        printf("FAIL null input\n");
+        failures++;
+    }
+    aoc_free_string(NULL);
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,36 @@
+//! Builds and runs the C test program in tests/c against the cdylib built by cargo
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Directory of the built library, the parent of the `deps` directory holding this test
+fn lib_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_c_program_calls_solvers() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+
+    let output = Command::new("make")
+        .arg("-C")
+        .arg(manifest_dir.join("tests/c"))
+        .arg(format!("LIB_DIR={}", lib_dir().display()))
+        .arg(format!("OUT_DIR={}", out_dir.display()))
+        .arg("-B")
+        .arg("run")
+        .output()
+        .expect("make should be installed to run the C test");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "C test failed:\n{}\n{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("ok   day01 part2: 6"));
+}